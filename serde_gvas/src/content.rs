@@ -0,0 +1,420 @@
+//! Buffer for values of the serde data model
+//!
+//! Types with a fixed layout in GVAS files (e.g. `FText` or `SoftObjectPath`) reach the
+//! Serializer only as `&T: Serialize` wrapped in a token newtype struct, and `Value` must hand
+//! them to `Deserialize` impls it doesn't know. Both sides therefore need a concrete copy of
+//! the value which can be inspected or deserialized again. `Value` can't be that copy, as it
+//! has no enums or options, and serde's own `Content` is private, so this is a minimal
+//! version of it without borrowed data.
+
+use serde::de::{self, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+    value::{MapDeserializer, SeqDeserializer}};
+use serde::ser::{self, Serialize};
+
+use error::{Error, Result};
+
+/// Any value of the serde data model
+///
+/// Types stored in GVAS files with a fixed layout (e.g. `FText`) are converted from and to
+/// `Content` in order to (de)serialize them with their derived implementations, as the
+/// (De)Serializer only gets access to them through serde.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Content {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Content>),
+    Unit,
+    Seq(Vec<Content>),
+    Map(Vec<(Content, Content)>),
+    UnitVariant(&'static str),
+    NewtypeVariant(&'static str, Box<Content>),
+    TupleVariant(&'static str, Vec<Content>),
+    StructVariant(&'static str, Vec<(Content, Content)>),
+}
+
+/// Serializes any value into `Content`
+///
+/// Newtype structs are transparent.
+pub(crate) struct ContentSerializer;
+
+impl ser::Serializer for ContentSerializer {
+    type Ok = Content;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Content> {
+        Ok(Content::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Content> {
+        Ok(Content::I8(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Content> {
+        Ok(Content::I16(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Content> {
+        Ok(Content::I32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Content> {
+        Ok(Content::I64(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Content> {
+        Ok(Content::U8(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Content> {
+        Ok(Content::U16(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Content> {
+        Ok(Content::U32(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Content> {
+        Ok(Content::U64(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Content> {
+        Ok(Content::F32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Content> {
+        Ok(Content::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Content> {
+        Ok(Content::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Content> {
+        Ok(Content::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Content> {
+        Ok(Content::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Content> {
+        Ok(Content::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Content> {
+        Ok(Content::Some(Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_unit(self) -> Result<Content> {
+        Ok(Content::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Content> {
+        Ok(Content::Unit)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Content> {
+        Ok(Content::UnitVariant(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Content> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<Content> {
+        Ok(Content::NewtypeVariant(variant, Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer { variant: None, elements: Vec::new() })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer { variant: None, elements: Vec::new() })
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer { variant: None, elements: Vec::new() })
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer { variant: Some(variant), elements: Vec::new() })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer { variant: None, entries: Vec::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<MapSerializer> {
+        Ok(MapSerializer { variant: None, entries: Vec::new(), key: None })
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<MapSerializer> {
+        Ok(MapSerializer { variant: Some(variant), entries: Vec::new(), key: None })
+    }
+}
+
+pub(crate) struct SeqSerializer {
+    variant: Option<&'static str>,
+    elements: Vec<Content>,
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.elements.push(value.serialize(ContentSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Content> {
+        Ok(match self.variant {
+            Some(variant) => Content::TupleVariant(variant, self.elements),
+            None => Content::Seq(self.elements),
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+pub(crate) struct MapSerializer {
+    variant: Option<&'static str>,
+    entries: Vec<(Content, Content)>,
+    /// Key whose value is serialized next
+    key: Option<Content>,
+}
+
+impl MapSerializer {
+    fn finish(self) -> Result<Content> {
+        Ok(match self.variant {
+            Some(variant) => Content::StructVariant(variant, self.entries),
+            None => Content::Map(self.entries),
+        })
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(ContentSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("serialize_value called before serialize_key")),
+        };
+        self.entries.push((key, value.serialize(ContentSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.entries.push((Content::String(key.to_string()), value.serialize(ContentSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.entries.push((Content::String(key.to_string()), value.serialize(ContentSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content> {
+        self.finish()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Content {
+    type Deserializer = Content;
+
+    fn into_deserializer(self) -> Content {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Content {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        match self {
+            Content::Bool(b) => v.visit_bool(b),
+            Content::I8(i) => v.visit_i8(i),
+            Content::I16(i) => v.visit_i16(i),
+            Content::I32(i) => v.visit_i32(i),
+            Content::I64(i) => v.visit_i64(i),
+            Content::U8(i) => v.visit_u8(i),
+            Content::U16(i) => v.visit_u16(i),
+            Content::U32(i) => v.visit_u32(i),
+            Content::U64(i) => v.visit_u64(i),
+            Content::F32(f) => v.visit_f32(f),
+            Content::F64(f) => v.visit_f64(f),
+            Content::Char(c) => v.visit_char(c),
+            Content::String(s) => v.visit_string(s),
+            Content::Bytes(b) => v.visit_byte_buf(b),
+            Content::None => v.visit_none(),
+            Content::Some(content) => v.visit_some(*content),
+            Content::Unit => v.visit_unit(),
+            Content::Seq(elements) => v.visit_seq(SeqDeserializer::new(elements.into_iter())),
+            Content::Map(entries) => v.visit_map(MapDeserializer::new(entries.into_iter())),
+            content @ Content::UnitVariant(_)
+            | content @ Content::NewtypeVariant(..)
+            | content @ Content::TupleVariant(..)
+            | content @ Content::StructVariant(..) => v.visit_enum(content),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        match self {
+            Content::None => v.visit_none(),
+            Content::Some(content) => v.visit_some(*content),
+            content => v.visit_some(content),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        match self {
+            Content::String(s) => v.visit_enum(s.into_deserializer()),
+            content => content.deserialize_any(v),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, v: V) -> Result<V::Value> {
+        v.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Accesses an enum variant, which is one of the `*Variant` contents
+impl<'de> EnumAccess<'de> for Content {
+    type Error = Error;
+    type Variant = Content;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Content)> {
+        let (variant, payload) = match self {
+            Content::UnitVariant(variant) => (variant, Content::Unit),
+            Content::NewtypeVariant(variant, content) => (variant, *content),
+            Content::TupleVariant(variant, elements) => (variant, Content::Seq(elements)),
+            Content::StructVariant(variant, entries) => (variant, Content::Map(entries)),
+            _ => return Err(de::Error::invalid_type(de::Unexpected::Other("non-enum content"), &"enum")),
+        };
+        let variant: de::value::StrDeserializer<Error> = variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, payload))
+    }
+}
+
+/// Accesses the payload of an enum variant
+impl<'de> VariantAccess<'de> for Content {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self {
+            Content::Unit => Ok(()),
+            _ => Err(de::Error::invalid_type(de::Unexpected::Other("variant with payload"), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, v: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, v)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], v: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, v)
+    }
+}
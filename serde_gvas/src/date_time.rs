@@ -0,0 +1,383 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use error::{Error, ErrorKind};
+
+/// Name of the newtype struct `DateTime` is serialized as
+pub(crate) const DATE_TIME_TOKEN: &str = "$serde_gvas::DateTime";
+/// Name of the newtype struct `Timespan` is serialized as
+pub(crate) const TIMESPAN_TOKEN: &str = "$serde_gvas::Timespan";
+
+const TICKS_PER_SECOND: i64 = 10_000_000;
+const TICKS_PER_MINUTE: i64 = 60 * TICKS_PER_SECOND;
+const TICKS_PER_HOUR: i64 = 60 * TICKS_PER_MINUTE;
+const TICKS_PER_DAY: i64 = 24 * TICKS_PER_HOUR;
+/// Days from 0001-01-01, which is Unreal's epoch, to 1970-01-01
+const DAYS_TO_UNIX_EPOCH: i64 = 719_162;
+/// Largest year whose start can be represented in ticks
+const MAX_YEAR: i64 = 29_228;
+
+/// Point in time as stored in a StructProperty of type `DateTime`, i.e. Unreal's `FDateTime`
+///
+/// It is formatted as ISO 8601 date and time in UTC, e.g. `2018-03-08T21:15:02.25Z`, and
+/// (de)serialized as such a string by human-readable formats. Years before 1 are negative
+/// and years after 9999 have more than four digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    /// 100 nanosecond ticks since 0001-01-01T00:00:00
+    pub ticks: i64,
+}
+
+/// Duration as stored in a StructProperty of type `Timespan`, i.e. Unreal's `FTimespan`
+///
+/// It is formatted as ISO 8601 duration, e.g. `P1DT2H30.5S`, and (de)serialized as such a string
+/// by human-readable formats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timespan {
+    /// 100 nanosecond ticks, which are negative for negative durations
+    pub ticks: i64,
+}
+
+impl DateTime {
+    /// Converts the date and time to a `SystemTime`
+    ///
+    /// Returns `None` if the platform can't represent it.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let since_epoch = Timespan { ticks: self.ticks.checked_sub(DAYS_TO_UNIX_EPOCH * TICKS_PER_DAY)? };
+        match since_epoch.to_duration() {
+            Some(duration) => UNIX_EPOCH.checked_add(duration),
+            None => UNIX_EPOCH.checked_sub(Timespan { ticks: since_epoch.ticks.checked_neg()? }.to_duration()?),
+        }
+    }
+}
+
+impl From<SystemTime> for DateTime {
+    fn from(time: SystemTime) -> DateTime {
+        let since_epoch = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => Timespan::from(duration).ticks,
+            Err(e) => -Timespan::from(e.duration()).ticks,
+        };
+        DateTime { ticks: since_epoch.saturating_add(DAYS_TO_UNIX_EPOCH * TICKS_PER_DAY) }
+    }
+}
+
+impl Timespan {
+    /// Converts the timespan to a `Duration`
+    ///
+    /// Returns `None` if the timespan is negative.
+    pub fn to_duration(&self) -> Option<Duration> {
+        if self.ticks < 0 {
+            return None;
+        }
+        Some(Duration::new((self.ticks / TICKS_PER_SECOND) as u64, (self.ticks % TICKS_PER_SECOND * 100) as u32))
+    }
+}
+
+/// Saturates at the largest timespan for durations of more than about 29000 years
+impl From<Duration> for Timespan {
+    fn from(duration: Duration) -> Timespan {
+        let ticks = i128::from(duration.as_secs()) * i128::from(TICKS_PER_SECOND) + i128::from(duration.subsec_nanos() / 100);
+        Timespan { ticks: ticks.min(i128::from(i64::MAX)) as i64 }
+    }
+}
+
+/// Returns the days since 0001-01-01 of a date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // years start in March, which moves the leap day to the end of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468 + DAYS_TO_UNIX_EPOCH
+}
+
+/// Returns year, month and day of the days since 0001-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days - DAYS_TO_UNIX_EPOCH + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Writes the ticks of a fraction of a second without trailing zeros
+fn write_fraction(f: &mut fmt::Formatter, ticks: i64) -> fmt::Result {
+    if ticks == 0 {
+        return Ok(());
+    }
+    let digits = format!("{:07}", ticks);
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+/// Parses a decimal number of seconds with up to 7 fractional digits into ticks
+fn parse_seconds(s: &str) -> Option<i64> {
+    let (seconds, fraction) = match s.find('.') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, "0"),
+    };
+    let fraction_len = fraction.len();
+    if fraction_len == 0 || fraction_len > 7 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = fraction.parse::<i64>().ok()? * 10i64.pow(7 - fraction_len as u32);
+    parse_number(seconds)?.checked_mul(TICKS_PER_SECOND)?.checked_add(fraction)
+}
+
+/// Parses a non-empty number of decimal digits without sign
+fn parse_number(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.ticks.div_euclid(TICKS_PER_DAY));
+        let time = self.ticks.rem_euclid(TICKS_PER_DAY);
+        if year < 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", year.abs(), month, day,
+            time / TICKS_PER_HOUR, time % TICKS_PER_HOUR / TICKS_PER_MINUTE, time % TICKS_PER_MINUTE / TICKS_PER_SECOND)?;
+        write_fraction(f, time % TICKS_PER_SECOND)?;
+        f.write_str("Z")
+    }
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS` with optional fractional seconds and an optional `Z`
+///
+/// The year has at least four digits and may be negative.
+impl FromStr for DateTime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<DateTime, Error> {
+        let parse = || -> Option<DateTime> {
+            let rest = s.strip_suffix('Z').unwrap_or(s);
+            let (date, time) = rest.split_at(rest.find('T')?);
+            let (negative, date) = match date.strip_prefix('-') {
+                Some(date) => (true, date),
+                None => (false, date),
+            };
+            let mut date = date.split('-');
+            let (year, month, day) = (date.next()?, date.next()?, date.next()?);
+            if date.next().is_some() || year.len() < 4 || month.len() != 2 || day.len() != 2 {
+                return None;
+            }
+            let (year, month, day) = (parse_number(year)?, parse_number(month)?, parse_number(day)?);
+            // the ticks only cover about 29000 years either way, which keeps the days from overflowing
+            if year > MAX_YEAR {
+                return None;
+            }
+            let year = if negative { -year } else { year };
+            let mut time = time.strip_prefix('T')?.splitn(3, ':');
+            let (hour, minute, seconds) = (parse_number(time.next()?)?, parse_number(time.next()?)?, parse_seconds(time.next()?)?);
+            if hour >= 24 || minute >= 60 || seconds >= 60 * TICKS_PER_SECOND {
+                return None;
+            }
+            let days = days_from_civil(year, month, day);
+            // days and months out of range end up in another month
+            if civil_from_days(days) != (year, month, day) {
+                return None;
+            }
+            // the start of the first day is before the smallest ticks
+            let ticks = i128::from(days) * i128::from(TICKS_PER_DAY) + i128::from(hour * TICKS_PER_HOUR + minute * TICKS_PER_MINUTE + seconds);
+            Some(DateTime { ticks: i64::try_from(ticks).ok()? })
+        };
+        parse().ok_or_else(|| Error::from(ErrorKind::InvalidDateTime(s.to_string())))
+    }
+}
+
+impl fmt::Display for Timespan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ticks < 0 {
+            f.write_str("-")?;
+        }
+        let ticks = self.ticks.unsigned_abs();
+        let (days, time) = (ticks / TICKS_PER_DAY as u64, ticks % TICKS_PER_DAY as u64);
+        f.write_str("P")?;
+        if days != 0 {
+            write!(f, "{}D", days)?;
+        }
+        if time == 0 && days != 0 {
+            return Ok(());
+        }
+        let time = time as i64;
+        let (hours, minutes, seconds) = (time / TICKS_PER_HOUR, time % TICKS_PER_HOUR / TICKS_PER_MINUTE, time % TICKS_PER_MINUTE);
+        f.write_str("T")?;
+        if hours != 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes != 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if seconds != 0 || time == 0 {
+            write!(f, "{}", seconds / TICKS_PER_SECOND)?;
+            write_fraction(f, seconds % TICKS_PER_SECOND)?;
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses an ISO 8601 duration consisting of days, hours, minutes and seconds, e.g. `-PT1M30S`
+///
+/// Only seconds may have a fractional part.
+impl FromStr for Timespan {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Timespan, Error> {
+        let parse = || -> Option<Timespan> {
+            let (negative, rest) = match s.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, s),
+            };
+            let rest = rest.strip_prefix('P')?;
+            let (days, time) = match rest.find('T') {
+                Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+                None => (rest, None),
+            };
+            // wide enough for the smallest timespan, which has no positive counterpart
+            let mut ticks = match days {
+                "" => 0,
+                days => i128::from(parse_number(days.strip_suffix('D')?)?) * i128::from(TICKS_PER_DAY),
+            };
+            if let Some(mut time) = time {
+                // the designators must appear in this order and at least one of them
+                let mut units = [('H', TICKS_PER_HOUR), ('M', TICKS_PER_MINUTE), ('S', TICKS_PER_SECOND)].iter();
+                if time.is_empty() {
+                    return None;
+                }
+                while !time.is_empty() {
+                    let end = time.find(|c: char| c.is_ascii_alphabetic())?;
+                    let unit = time[end..].chars().next()?;
+                    let &(unit, unit_ticks) = units.find(|&&(c, _)| c == unit)?;
+                    let value = match unit {
+                        'S' => parse_seconds(&time[..end])?,
+                        _ => parse_number(&time[..end])?.checked_mul(unit_ticks)?,
+                    };
+                    ticks += i128::from(value);
+                    time = &time[end + 1..];
+                }
+            } else if days.is_empty() {
+                return None;
+            }
+            Some(Timespan { ticks: i64::try_from(if negative { -ticks } else { ticks }).ok()? })
+        };
+        parse().ok_or_else(|| Error::from(ErrorKind::InvalidTimespan(s.to_string())))
+    }
+}
+
+/// Serializes the ticks, or their ISO 8601 string if the format is human-readable
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_newtype_struct(DATE_TIME_TOKEN, &self.to_string())
+        } else {
+            s.serialize_newtype_struct(DATE_TIME_TOKEN, &self.ticks)
+        }
+    }
+}
+
+/// Serializes the ticks, or their ISO 8601 string if the format is human-readable
+impl Serialize for Timespan {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_newtype_struct(TIMESPAN_TOKEN, &self.to_string())
+        } else {
+            s.serialize_newtype_struct(TIMESPAN_TOKEN, &self.ticks)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<DateTime, D::Error> {
+        d.deserialize_newtype_struct(DATE_TIME_TOKEN, TicksVisitor(PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for Timespan {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Timespan, D::Error> {
+        d.deserialize_newtype_struct(TIMESPAN_TOKEN, TicksVisitor(PhantomData))
+    }
+}
+
+/// Types consisting of ticks, which are parsed from their string, the ticks themselves or the
+/// fields of their struct
+trait Ticks: FromStr<Err = Error> {
+    const EXPECTING: &'static str;
+    fn from_ticks(ticks: i64) -> Self;
+}
+
+impl Ticks for DateTime {
+    const EXPECTING: &'static str = "an ISO 8601 date and time or DateTime struct";
+
+    fn from_ticks(ticks: i64) -> DateTime {
+        DateTime { ticks }
+    }
+}
+
+impl Ticks for Timespan {
+    const EXPECTING: &'static str = "an ISO 8601 duration or Timespan struct";
+
+    fn from_ticks(ticks: i64) -> Timespan {
+        Timespan { ticks }
+    }
+}
+
+struct TicksVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Ticks> Visitor<'de> for TicksVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(T::EXPECTING)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<T, D::Error> {
+        if d.is_human_readable() {
+            d.deserialize_str(self)
+        } else {
+            d.deserialize_i64(self)
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, ticks: i64) -> Result<T, E> {
+        Ok(T::from_ticks(ticks))
+    }
+
+    fn visit_u64<E: de::Error>(self, ticks: u64) -> Result<T, E> {
+        match i64::try_from(ticks) {
+            Ok(ticks) => Ok(T::from_ticks(ticks)),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Unsigned(ticks), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map_err(E::custom)
+    }
+
+    /// Visits the fields of a StructProperty
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut ticks = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "Ticks" => ticks = Some(map.next_value()?),
+                _ => { map.next_value::<de::IgnoredAny>()?; },
+            }
+        }
+        ticks.map(T::from_ticks).ok_or_else(|| de::Error::missing_field("Ticks"))
+    }
+}
@@ -1,134 +1,707 @@
-use std::io::{Read, Cursor};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
 use std::str::FromStr;
 
 use byteorder::{ReadBytesExt, LE};
-use encoding_rs::{Encoding, WINDOWS_1252, UTF_16LE};
-use serde::{self, de::{Visitor, SeqAccess, MapAccess, DeserializeSeed, IntoDeserializer}};
-use void::Void;
+use encoding_rs::{WINDOWS_1252, UTF_16LE};
+use serde::{self, de::{Visitor, SeqAccess, MapAccess, DeserializeSeed, DeserializeOwned, IntoDeserializer, value::SeqDeserializer}};
+use void::{ResultVoidExt, Void};
 
+use delegate::{Delegate, MulticastDelegate, DELEGATE_TOKEN, MULTICAST_DELEGATE_TOKEN};
 use error::{Result, Error, ErrorKind};
+use field_path::{FieldPath, FIELD_PATH_TOKEN};
+use guid::Guid;
+use header::{GvasHeader, PackageVersion};
+use native::{NativeField, native_struct_fields};
+use struct_codec::StructCodecRegistry;
+use soft_object::{SoftObjectPath, SOFT_OBJECT_PATH_TOKEN};
+use text::{FText, TextHistory, FormatArgumentValue, NumberFormattingOptions, TEXT_TOKEN};
+use value::{Value, Property, StructType, VALUE_TOKEN};
 
-//pub fn from_bytes(bytes: &[u8]) -> Result<Deserializer<Cursor<&[u8]>>> {
-//    Deserializer::new(Cursor::new(bytes))
-//}
-//
-//pub fn from_reader<R: Read>(r: R) -> Result<Deserializer<R>> {
-//    Deserializer::new(r)
-//}
+mod options;
+mod reader;
+
+pub use self::options::DeserializerOptions;
+pub(crate) use self::reader::{CountingReader, TotalBytesExceeded};
+
+/// Deserialize an instance of `T` from a whole GVAS file, including its header
+pub fn from_reader<R: Read, T: DeserializeOwned>(r: R) -> Result<T> {
+    from_reader_with_header(r).map(|(_header, t)| t)
+}
+
+/// Deserialize an instance of `T` from a whole GVAS file, also returning the file's header
+///
+/// The bytes following the properties are kept in the header's `trailing_bytes`.
+pub fn from_reader_with_header<R: Read, T: DeserializeOwned>(r: R) -> Result<(GvasHeader, T)> {
+    from_reader_with_codecs(r, &StructCodecRegistry::default())
+}
+
+/// Deserialize an instance of `T` from a whole GVAS file like `from_reader_with_header`,
+/// decoding structs with the given codecs
+pub fn from_reader_with_codecs<R: Read, T: DeserializeOwned>(r: R, codecs: &StructCodecRegistry) -> Result<(GvasHeader, T)> {
+    let options = DeserializerOptions { struct_codecs: codecs.clone(), ..DeserializerOptions::default() };
+    from_reader_with_options(r, &options)
+}
+
+/// Deserialize an instance of `T` from a whole GVAS file like `from_reader_with_header`,
+/// within the limits of the given options
+pub fn from_reader_with_options<R: Read, T: DeserializeOwned>(mut r: R, options: &DeserializerOptions) -> Result<(GvasHeader, T)> {
+    let mut r = CountingReader::new(&mut r, 0).with_limit(options.max_total_bytes);
+    let mut header = GvasHeader::parse(&mut r, options.max_string_length).map_err(|e| e.at_offset(r.position()))?;
+    let t = {
+        let position = r.position();
+        let mut de = Deserializer::at_position(&mut r, &header, position).with_options(options.clone());
+        T::deserialize(RootDeserializer { de: &mut de, class_name: &header.save_game_class_name })
+            .map_err(|e| e.at_offset(de.r.position()))?
+    };
+    r.read_to_end(&mut header.trailing_bytes).map_err(|e| Error::from(e).at_offset(r.position()))?;
+    Ok((header, t))
+}
+
+/// Deserialize an instance of `T` from the bytes of a whole GVAS file
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    from_reader(bytes)
+}
+
+/// Deserialize an instance of `T` from the GVAS file at the given path
+pub fn from_path<P: AsRef<Path>, T: DeserializeOwned>(path: P) -> Result<T> {
+    from_reader(BufReader::new(File::open(path)?))
+}
+
+/// Package version from which StructProperty tags contain the GUID of the struct
+pub(crate) const VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG: u32 = 441;
+/// Package version from which arrays of structs contain a tag describing their elements
+pub(crate) const VER_UE4_INNER_ARRAY_TAG_INFO: u32 = 500;
+/// Package version from which property tags can contain the GUID of the property
+pub(crate) const VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG: u32 = 503;
+/// UE5 package version from which vectors and other math structs consist of doubles instead of floats
+pub(crate) const VER_UE5_LARGE_WORLD_COORDINATES: u32 = 1004;
+/// UE5 package version from which soft object paths store package and asset name separately
+pub(crate) const VER_UE5_FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES: u32 = 1007;
 
 pub struct Deserializer<R: Read> {
-    r: R,
-    read: usize,
+    r: CountingReader<R>,
+    package_version: PackageVersion,
+    options: DeserializerOptions,
+    /// Count of the structs and texts currently being parsed
+    depth: usize,
+    /// Names of the properties currently being parsed, which struct types are looked up by
+    path: Vec<String>,
 }
 
 // Format starts with header, followed by struct-name, followed by Struct.
-// We ignore the header and let the user parse that before he hands over the reader to us.
+// Header and struct-name are parsed by `GvasHeader`, the Deserializer only needs the versions from it.
 //
 // Struct: Map<String, Property>
-// Property: (TypeString, lenI32, unknownI32, Value)
-// Value: Struct | Array | Object | Primitive | External
-// Primitive: Bool | Int | QWord | Float | Str | Byte (actually enum)
-// Byte (actually enum): (EnumNameString, VariantString)
-// External (???): LinearColor | CharacterDNA | CharacterCustomization | NameProperty | ...
+// Property: (TypeString, lenI32, arrayIndexI32, TagData, HasGuidU8, Value)
+// Value is exactly len bytes long, by which ignored properties are skipped and those of unknown types kept as raw bytes
+// TagData: StructTag | BoolU8 | EnumNameString | InnerTypeString | MapTag | ()
+// StructTag: (StructNameString, Guid)
+// MapTag: (KeyTypeString, ValueTypeString)
+// Value: Struct | Array | Map | Set | Object | SoftObject | LazyObject | Primitive | External
+// SoftObject (and SoftClass): (AssetPathNameString, SubPathString) | (PackageNameString, AssetNameString, SubPathString) from UE5 1007
+// LazyObject: Guid
+// Interface: ObjectString
+// Delegate: (ObjectString, FunctionNameString)
+// MulticastDelegate (inline and sparse): (countU32, [Delegate])
+// FieldPath: (countU32, [NameString], OwnerString)
+// Primitive: Bool | Int8 | Int16 | Int | Int64 | UInt16 | UInt32 | UInt64 | QWord | Float | Double | Str | Text | Name | Byte | Enum
+// Text: (flagsU32, historyTypeI8, TextHistory)
+// FormatArgument: (typeI8, Int64 | UInt64 | Float | Double | Text | GenderU8)
+// ArgumentFormat argument: (NameString, typeU8, Int | Float | Text | GenderU8)
+// Name: String
+// Byte: u8 (if the enum name is `None`) | VariantString
+// Enum: VariantString
+// Struct: Map<String, Property> | NativeStruct
+// NativeStruct: LinearColor | ... | decoded by a codec of the StructCodecRegistry
+// Structs in maps, sets and arrays before version 500 have no struct name, it's taken from the Rust type or DeserializerOptions::struct_types
+// Array: (countU32, InnerTag, [Value])
+// Array of Byte: (countU32, [u8]) if its length is 4 + count, else (countU32, [VariantString])
+// InnerTag: Property without Value, only for arrays of structs
+// Map: (removedCountU32, [Value], countU32, [(Value, Value)])
+// Set: (removedCountU32, [Value], countU32, [Value])
 
 impl<R: Read> Deserializer<R> {
-    /// Create a new Deserializer and return the name of the serialized struct
+    /// Create a new Deserializer for the properties following the given header
+    ///
+    /// This method assumes that the header is already parsed (e.g. with `GvasHeader::read`)
+    /// and the reader starts at the first property (i.e. it's name).
+    /// The header is needed as the layout of the property tags changed between versions.
+    /// Offsets of errors are counted from the first property.
+    pub fn new(r: R, header: &GvasHeader) -> Deserializer<R> {
+        Deserializer::at_position(r, header, 0)
+    }
+
+    /// Create a new Deserializer whose reader is at the given offset in the file
+    fn at_position(r: R, header: &GvasHeader, position: usize) -> Deserializer<R> {
+        Deserializer {
+            r: CountingReader::new(r, position),
+            package_version: header.package_versions(),
+            options: DeserializerOptions::default(),
+            depth: 0,
+            path: Vec::new(),
+        }
+    }
+
+    /// Decode structs with the given codecs
+    pub fn with_struct_codecs(mut self, codecs: StructCodecRegistry) -> Deserializer<R> {
+        self.options.struct_codecs = codecs;
+        self
+    }
+
+    /// Use the given options, including their struct codecs
     ///
-    /// This method assumes that the header is already parsed and the reader starts
-    /// at the first value (i.e. it's name).
-    pub fn new(mut r: R) -> Result<(Deserializer<R>, String)> {
-        let (name, len) = parse_string(&mut r, 0)?;
-        Ok((Deserializer {
-            r,
-            read: len,
-        }, name))
+    /// The maximum total bytes limits the offset up to which is read, which is counted from the
+    /// first property for Deserializers created with `new`.
+    pub fn with_options(mut self, options: DeserializerOptions) -> Deserializer<R> {
+        self.r = self.r.with_limit(options.max_total_bytes);
+        self.options = options;
+        self
+    }
+
+    /// Skips the body of a property of the given length
+    fn skip(&mut self, len: u32) -> Result<()> {
+        trace!("skip: {}", len);
+        let skipped = io::copy(&mut (&mut self.r).take(u64::from(len)), &mut io::sink())?;
+        if skipped != u64::from(len) {
+            return Err(Error::new(ErrorKind::UnexpectedEof, self.r.position()));
+        }
+        Ok(())
+    }
+
+    /// Fails unless the body starting at the given offset has the given length, if it is known
+    fn check_size(&self, start: usize, len: Option<u32>) -> Result<()> {
+        let actual = self.r.position() - start;
+        match len {
+            Some(expected) if expected as usize != actual => Err(Error::new(ErrorKind::SizeMismatch { expected, actual }, start)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs the given parse function within the property of the given name
+    fn within<T, F: FnOnce(&mut Self) -> Result<T>>(&mut self, name: &str, f: F) -> Result<T> {
+        self.path.push(name.to_string());
+        let res = f(self);
+        self.path.pop();
+        res
+    }
+
+    /// Returns the struct type of the structs in the current property, if it is given in the options
+    ///
+    /// The key is `Key` or `Value` for the keys and values of maps.
+    fn struct_type(&self, key: Option<&str>) -> Option<String> {
+        if self.options.struct_types.is_empty() {
+            return None;
+        }
+        let mut path = self.path.join(".");
+        if let Some(key) = key {
+            path.push('.');
+            path.push_str(key);
+        }
+        self.options.struct_types.get(&path).cloned()
+    }
+
+    /// Runs the given parse function one nesting level deeper, failing if it's too deep
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T>>(&mut self, f: F) -> Result<T> {
+        if self.depth >= self.options.max_depth {
+            return Err(Error::new(ErrorKind::NestingTooDeep(self.options.max_depth), self.r.position()));
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
     }
 
-    fn parse_type(&mut self) -> Result<(PropertyType, u32)> {
+    fn parse_type(&mut self) -> Result<PropertyTag> {
         trace!("parse_type");
         let s = self.parse_str(None)?;
-        let typ = PropertyType::from_str(&s).unwrap();
+        let typ = PropertyType::from_str(&s).void_unwrap();
         let len = self.r.read_u32::<LE>()?;
-        let _unknown = self.r.read_u32::<LE>()?;
-        self.read += 8;
-        trace!("{:?}, {}, {}", typ, len, _unknown);
-        Ok((typ, len))
+        let array_index = self.r.read_u32::<LE>()?;
+        trace!("{:?}, {}, {}", typ, len, array_index);
+        let mut tag = PropertyTag::new(typ, Some(len));
+        tag.array_index = array_index;
+        match tag.typ {
+            PropertyType::Struct => {
+                let (struct_name, struct_guid) = self.parse_struct_header()?;
+                tag.struct_name = Some(struct_name);
+                tag.struct_guid = struct_guid;
+            }
+            PropertyType::Bool => tag.bool_value = Some(self.parse_bool(None)?),
+            PropertyType::Byte | PropertyType::Enum => tag.enum_name = Some(self.parse_str(None)?),
+            PropertyType::Array | PropertyType::Set => tag.inner_type = Some(self.parse_type_in_array()?),
+            PropertyType::Map => {
+                tag.inner_type = Some(self.parse_type_in_array()?);
+                tag.value_type = Some(self.parse_type_in_array()?);
+            }
+            _ => {}
+        }
+        tag.guid = self.parse_property_guid()?;
+        Ok(tag)
+    }
+
+    /// Parses the optional GUID at the end of a property's tag
+    fn parse_property_guid(&mut self) -> Result<Option<Guid>> {
+        if self.package_version.ue4 < VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG {
+            return Ok(None);
+        }
+        trace!("parse_property_guid");
+        if !self.parse_bool(None)? {
+            return Ok(None);
+        }
+        self.parse_guid().map(Some)
     }
 
+    /// Parses the part of a StructProperty's tag following its length
+    ///
+    /// Returns the struct type name and the GUID following it, which is all zeros for
+    /// every struct we've seen so far.
+    fn parse_struct_header(&mut self) -> Result<(String, Guid)> {
+        trace!("parse_struct_header");
+        let name = self.parse_str(None)?;
+        let mut guid = Guid::default();
+        if self.package_version.ue4 >= VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG {
+            guid = self.parse_guid()?;
+        }
+        trace!("{:?}", name);
+        Ok((name, guid))
+    }
+
+    fn parse_guid(&mut self) -> Result<Guid> {
+        trace!("parse_guid");
+        let mut guid = Guid::default();
+        self.r.read_exact(&mut guid.0)?;
+        trace!("{}", guid);
+        Ok(guid)
+    }
+
+    /// Parses the type of the elements of an ArrayProperty or of the keys or values of a MapProperty
     fn parse_type_in_array(&mut self) -> Result<PropertyType> {
         trace!("parse_type_in_array");
         let s = self.parse_str(None)?;
-        let typ = PropertyType::from_str(&s).unwrap();
+        let typ = PropertyType::from_str(&s).void_unwrap();
         trace!("{:?}", typ);
         Ok(typ)
     }
 
+    /// Parses a bool, which is either part of the tag or an element of an array
     fn parse_bool(&mut self, len: Option<u32>) -> Result<bool> {
         trace!("parse_bool");
-        // len for bool is broken, ignore it
-        self.read += 1;
-        let b = self.r.read_u8()? == 1;
+        if let Some(len) = len.filter(|&len| len != 1) {
+            return Err(Error::new(ErrorKind::InvalidBoolLength(len), self.r.position()));
+        }
+        let offset = self.r.position();
+        let b = match self.r.read_u8()? {
+            0 => false,
+            1 => true,
+            b => return Err(Error::new(ErrorKind::InvalidBool(u32::from(b)), offset)),
+        };
         trace!("{}", b);
         Ok(b)
     }
 
+    fn parse_int8(&mut self, len: Option<u32>) -> Result<i8> {
+        trace!("parse_int8");
+        if let Some(len) = len.filter(|&len| len != 1) {
+            return Err(Error::new(ErrorKind::InvalidInt8Length(len), self.r.position()));
+        }
+        let i = self.r.read_i8()?;
+        trace!("{}", i);
+        Ok(i)
+    }
+
+    fn parse_int16(&mut self, len: Option<u32>) -> Result<i16> {
+        trace!("parse_int16");
+        if let Some(len) = len.filter(|&len| len != 2) {
+            return Err(Error::new(ErrorKind::InvalidInt16Length(len), self.r.position()));
+        }
+        let i = self.r.read_i16::<LE>()?;
+        trace!("{}", i);
+        Ok(i)
+    }
+
     fn parse_int(&mut self, len: Option<u32>) -> Result<i32> {
         trace!("parse_int");
-        if len.is_some() && len.unwrap() != 4 {
-            return Err(Error::new(ErrorKind::InvalidIntLength(len.unwrap()), self.read));
+        if let Some(len) = len.filter(|&len| len != 4) {
+            return Err(Error::new(ErrorKind::InvalidIntLength(len), self.r.position()));
         }
         let i = self.r.read_i32::<LE>()?;
-        self.read += 4;
+        trace!("{}", i);
+        Ok(i)
+    }
+
+    fn parse_int64(&mut self, len: Option<u32>) -> Result<i64> {
+        trace!("parse_int64");
+        if let Some(len) = len.filter(|&len| len != 8) {
+            return Err(Error::new(ErrorKind::InvalidInt64Length(len), self.r.position()));
+        }
+        let i = self.r.read_i64::<LE>()?;
+        trace!("{}", i);
+        Ok(i)
+    }
+
+    fn parse_uint16(&mut self, len: Option<u32>) -> Result<u16> {
+        trace!("parse_uint16");
+        if let Some(len) = len.filter(|&len| len != 2) {
+            return Err(Error::new(ErrorKind::InvalidUInt16Length(len), self.r.position()));
+        }
+        let i = self.r.read_u16::<LE>()?;
+        trace!("{}", i);
+        Ok(i)
+    }
+
+    fn parse_uint32(&mut self, len: Option<u32>) -> Result<u32> {
+        trace!("parse_uint32");
+        if let Some(len) = len.filter(|&len| len != 4) {
+            return Err(Error::new(ErrorKind::InvalidUInt32Length(len), self.r.position()));
+        }
+        let i = self.r.read_u32::<LE>()?;
+        trace!("{}", i);
+        Ok(i)
+    }
+
+    fn parse_uint64(&mut self, len: Option<u32>) -> Result<u64> {
+        trace!("parse_uint64");
+        if let Some(len) = len.filter(|&len| len != 8) {
+            return Err(Error::new(ErrorKind::InvalidUInt64Length(len), self.r.position()));
+        }
+        let i = self.r.read_u64::<LE>()?;
         trace!("{}", i);
         Ok(i)
     }
 
     fn parse_qword(&mut self, len: Option<u32>) -> Result<i64> {
         trace!("parse_qword");
-        if len.is_some() && len.unwrap() != 8 {
-            return Err(Error::new(ErrorKind::InvalidQwordLength(len.unwrap()), self.read));
+        if let Some(len) = len.filter(|&len| len != 8) {
+            return Err(Error::new(ErrorKind::InvalidQwordLength(len), self.r.position()));
         }
         let i = self.r.read_i64::<LE>()?;
-        self.read += 8;
         trace!("{}", i);
         Ok(i)
     }
 
     fn parse_float(&mut self, len: Option<u32>) -> Result<f32> {
         trace!("parse_float");
-        if len.is_some() && len.unwrap() != 4 {
-            return Err(Error::new(ErrorKind::InvalidFloatLength(len.unwrap()), self.read));
+        if let Some(len) = len.filter(|&len| len != 4) {
+            return Err(Error::new(ErrorKind::InvalidFloatLength(len), self.r.position()));
         }
         let f = self.r.read_f32::<LE>()?;
-        self.read += 4;
+        trace!("{}", f);
+        Ok(f)
+    }
+
+    fn parse_double(&mut self, len: Option<u32>) -> Result<f64> {
+        trace!("parse_double");
+        if let Some(len) = len.filter(|&len| len != 8) {
+            return Err(Error::new(ErrorKind::InvalidDoubleLength(len), self.r.position()));
+        }
+        let f = self.r.read_f64::<LE>()?;
         trace!("{}", f);
         Ok(f)
     }
 
     fn parse_str(&mut self, len: Option<u32>) -> Result<String> {
         trace!("parse_str");
-        let (s, slen) = parse_string(&mut self.r, self.read)?;
-        if len.is_some() && len.unwrap() as usize != slen {
-            return Err(Error::new(ErrorKind::InvalidStringLength(len.unwrap()), self.read));
+        let start = self.r.position();
+        let s = parse_string(&mut self.r, self.options.max_string_length)?;
+        if let Some(len) = len.filter(|&len| len as usize != self.r.position() - start) {
+            return Err(Error::new(ErrorKind::InvalidStringLength(len), start));
         }
-        self.read += slen;
         trace!("{:?}", s);
         Ok(s)
     }
 
-    /// Parses a ByteProperty, which is an enum variant
-    ///
-    /// Returns a tuple containing the enum-name and the enum varinant
-    fn parse_byte(&mut self, len: Option<u32>) -> Result<(String, String)> {
+    /// Parses a ByteProperty holding a raw byte, whose enum name is `None`
+    fn parse_byte(&mut self, len: Option<u32>) -> Result<u8> {
         trace!("parse_byte");
-        let (name, nlen) = parse_string(&mut self.r, self.read)?;
-        let (variant, vlen) = parse_string(&mut self.r, self.read)?;
-        if len.is_some() && len.unwrap() as usize != nlen + vlen {
-            return Err(Error::new(ErrorKind::InvalidStringLength(len.unwrap()), self.read));
+        if let Some(len) = len.filter(|&len| len != 1) {
+            return Err(Error::new(ErrorKind::InvalidByteLength(len), self.r.position()));
+        }
+        let b = self.r.read_u8()?;
+        trace!("{}", b);
+        Ok(b)
+    }
+
+    /// Parses a ByteProperty holding an enum variant
+    ///
+    /// Returns the enum variant. The enum name is part of the tag.
+    fn parse_byte_variant(&mut self, len: Option<u32>) -> Result<String> {
+        trace!("parse_byte_variant");
+        let variant = self.parse_str(len)?;
+        trace!("{:?}", variant);
+        Ok(variant)
+    }
+
+    /// Parses the raw bytes of an array of bytes at once
+    fn parse_bytes(&mut self, count: u32) -> Result<Vec<u8>> {
+        trace!("parse_bytes: {}", count);
+        // not preallocated, as the count may be garbage
+        let mut bytes = Vec::new();
+        (&mut self.r).take(u64::from(count)).read_to_end(&mut bytes)?;
+        if bytes.len() != count as usize {
+            return Err(Error::new(ErrorKind::UnexpectedEof, self.r.position()));
         }
-        self.read += nlen + vlen;
-        trace!("{:?}, {:?}", name, variant);
-        Ok((name, variant))
+        Ok(bytes)
+    }
+
+    /// Parses an EnumProperty, whose variant is stored as name
+    ///
+    /// Returns the enum variant, which is usually prefixed with the enum name (e.g. `EFoo::Bar`).
+    /// The enum name is part of the tag.
+    fn parse_enum(&mut self, len: Option<u32>) -> Result<String> {
+        trace!("parse_enum");
+        let variant = self.parse_str(len)?;
+        trace!("{:?}", variant);
+        Ok(variant)
+    }
+
+    fn parse_name(&mut self, len: Option<u32>) -> Result<String> {
+        trace!("parse_name");
+        let name = self.parse_str(len)?;
+        trace!("{:?}", name);
+        Ok(name)
+    }
+
+    /// Parses the body of a struct with a codec or a native struct, whose value is raw binary
+    /// instead of a property list
+    ///
+    /// Returns the fields of the struct in order.
+    fn parse_native_struct(&mut self, name: &str, len: Option<u32>) -> Result<Option<Vec<Property>>> {
+        trace!("parse_native_struct: {:?}", name);
+        if let Some(codec) = self.options.struct_codecs.get(name) {
+            // the codec mustn't read past the struct if its length is known
+            let start = self.r.position();
+            let limit = len.map_or(u64::MAX, u64::from);
+            let mut r = (&mut self.r).take(limit);
+            let fields = (codec.decode)(&mut r)?;
+            trace!("{:?}", fields);
+            // nor stop short of its end
+            self.check_size(start, len)?;
+            return Ok(Some(fields));
+        }
+        let fields = match native_struct_fields(name) {
+            Some(fields) => fields,
+            None => return Ok(None),
+        };
+        let start = self.r.position();
+        let properties = self.parse_native_fields(fields)?;
+        // e.g. UE4 vectors of floats in a file claiming to be from UE5
+        self.check_size(start, len)?;
+        Ok(Some(properties))
+    }
+
+    fn parse_native_fields(&mut self, fields: &[(&str, NativeField)]) -> Result<Vec<Property>> {
+        let mut res = Vec::with_capacity(fields.len());
+        for &(name, field) in fields {
+            let value = self.parse_native_field(field).map_err(|e| e.in_property(name).at_offset(self.r.position()))?;
+            res.push(Property::new(name, value));
+        }
+        trace!("{:?}", res);
+        Ok(res)
+    }
+
+    fn parse_native_field(&mut self, field: NativeField) -> Result<Value> {
+        Ok(match field {
+            NativeField::Real if self.package_version.ue5 >= VER_UE5_LARGE_WORLD_COORDINATES =>
+                Value::Double(self.parse_double(None)?),
+            NativeField::Real | NativeField::Float => Value::Float(self.parse_float(None)?),
+            NativeField::Bool => Value::Bool(self.parse_bool(None)?),
+            NativeField::Byte => Value::UInt8(self.parse_byte(None)?),
+            NativeField::Int => Value::Int(self.parse_int(None)?),
+            NativeField::Int64 => Value::Int64(self.parse_int64(None)?),
+            NativeField::UInt32 => Value::UInt32(self.parse_uint32(None)?),
+            NativeField::NameArray => {
+                let count = self.parse_count()?;
+                let mut elements = Vec::new();
+                for _ in 0..count {
+                    elements.push(Value::Name(self.parse_str(None)?));
+                }
+                Value::Array { inner_type: PropertyType::Name.as_str().to_string(), struct_type: None, elements }
+            }
+            NativeField::Struct(type_name, fields) => Value::Struct {
+                type_name: type_name.to_string(),
+                guid: Guid::default(),
+                properties: self.parse_native_fields(fields)?,
+            },
+        })
+    }
+
+    /// Parses the body of a TextProperty, i.e. an `FText`
+    fn parse_text(&mut self) -> Result<FText> {
+        // texts are nested in texts they are formatted or transformed into
+        self.nested(Self::parse_text_body)
+    }
+
+    fn parse_text_body(&mut self) -> Result<FText> {
+        trace!("parse_text");
+        let flags = self.r.read_u32::<LE>()?;
+        let history_offset = self.r.position();
+        let history_type = self.r.read_i8()?;
+        trace!("{}, {}", flags, history_type);
+        let history = match history_type {
+            -1 => TextHistory::None {
+                culture_invariant_string: if self.parse_ubool()? { Some(self.parse_str(None)?) } else { None },
+            },
+            0 => TextHistory::Base {
+                namespace: self.parse_str(None)?,
+                key: self.parse_str(None)?,
+                source_string: self.parse_str(None)?,
+            },
+            1 => TextHistory::NamedFormat {
+                source_format: Box::new(self.parse_text()?),
+                arguments: self.parse_named_format_arguments()?,
+            },
+            2 => TextHistory::OrderedFormat {
+                source_format: Box::new(self.parse_text()?),
+                arguments: {
+                    let count = self.parse_count()?;
+                    (0..count).map(|_| self.parse_format_argument()).collect::<Result<_>>()?
+                },
+            },
+            3 => TextHistory::ArgumentFormat {
+                source_format: Box::new(self.parse_text()?),
+                arguments: {
+                    let count = self.parse_count()?;
+                    (0..count).map(|_| self.parse_format_argument_data()).collect::<Result<_>>()?
+                },
+            },
+            4 => TextHistory::AsNumber {
+                source_value: self.parse_format_argument()?,
+                format_options: self.parse_number_formatting_options()?,
+                target_culture: self.parse_str(None)?,
+            },
+            5 => TextHistory::AsPercent {
+                source_value: self.parse_format_argument()?,
+                format_options: self.parse_number_formatting_options()?,
+                target_culture: self.parse_str(None)?,
+            },
+            6 => TextHistory::AsCurrency {
+                currency_code: self.parse_str(None)?,
+                source_value: self.parse_format_argument()?,
+                format_options: self.parse_number_formatting_options()?,
+                target_culture: self.parse_str(None)?,
+            },
+            7 => TextHistory::AsDate {
+                source_date_time: self.parse_qword(None)?,
+                date_style: self.parse_int8(None)?,
+                time_zone: self.parse_str(None)?,
+                target_culture: self.parse_str(None)?,
+            },
+            8 => TextHistory::AsTime {
+                source_date_time: self.parse_qword(None)?,
+                time_style: self.parse_int8(None)?,
+                time_zone: self.parse_str(None)?,
+                target_culture: self.parse_str(None)?,
+            },
+            9 => TextHistory::AsDateTime {
+                source_date_time: self.parse_qword(None)?,
+                date_style: self.parse_int8(None)?,
+                time_style: self.parse_int8(None)?,
+                time_zone: self.parse_str(None)?,
+                target_culture: self.parse_str(None)?,
+            },
+            10 => TextHistory::Transform {
+                source_text: Box::new(self.parse_text()?),
+                transform_type: self.parse_int8(None)? as u8,
+            },
+            11 => TextHistory::StringTableEntry {
+                table_id: self.parse_str(None)?,
+                key: self.parse_str(None)?,
+            },
+            12 => {
+                let generator_type = self.parse_str(None)?;
+                let mut contents = Vec::new();
+                if generator_type != "None" {
+                    let count = self.parse_count()?;
+                    contents = self.parse_bytes(count)?;
+                }
+                TextHistory::TextGenerator { generator_type, contents }
+            }
+            typ => return Err(Error::new(ErrorKind::InvalidTextHistory(typ), history_offset)),
+        };
+        Ok(FText { flags, history })
+    }
+
+    /// Parses the arguments of a NamedFormat text history
+    fn parse_named_format_arguments(&mut self) -> Result<Vec<(String, FormatArgumentValue)>> {
+        let count = self.parse_count()?;
+        let mut arguments = Vec::new();
+        for _ in 0..count {
+            let name = self.parse_str(None)?;
+            arguments.push((name, self.parse_format_argument()?));
+        }
+        Ok(arguments)
+    }
+
+    fn parse_format_argument(&mut self) -> Result<FormatArgumentValue> {
+        trace!("parse_format_argument");
+        let offset = self.r.position();
+        let typ = self.parse_int8(None)?;
+        Ok(match typ {
+            0 => FormatArgumentValue::Int(self.parse_int64(None)?),
+            1 => FormatArgumentValue::UInt(self.parse_uint64(None)?),
+            2 => FormatArgumentValue::Float(self.parse_float(None)?),
+            3 => FormatArgumentValue::Double(self.parse_double(None)?),
+            4 => FormatArgumentValue::Text(Box::new(self.parse_text()?)),
+            5 => FormatArgumentValue::Gender(self.parse_int8(None)? as u8),
+            typ => return Err(Error::new(ErrorKind::InvalidFormatArgumentType(typ), offset)),
+        })
+    }
+
+    /// Parses an argument of an ArgumentFormat text history
+    ///
+    /// Unlike other format arguments, its type is a u8, integers are int32 and there are no UInt
+    /// and Double arguments.
+    fn parse_format_argument_data(&mut self) -> Result<(String, FormatArgumentValue)> {
+        trace!("parse_format_argument_data");
+        let name = self.parse_str(None)?;
+        let offset = self.r.position();
+        let typ = self.r.read_u8()?;
+        let value = match typ {
+            0 => FormatArgumentValue::Int(i64::from(self.parse_int(None)?)),
+            2 => FormatArgumentValue::Float(self.parse_float(None)?),
+            4 => FormatArgumentValue::Text(Box::new(self.parse_text()?)),
+            5 => FormatArgumentValue::Gender(self.r.read_u8()?),
+            typ => return Err(Error::new(ErrorKind::InvalidFormatArgumentType(typ as i8), offset)),
+        };
+        Ok((name, value))
+    }
+
+    /// Parses the optional formatting options of a text formatted as number
+    fn parse_number_formatting_options(&mut self) -> Result<Option<NumberFormattingOptions>> {
+        if !self.parse_ubool()? {
+            return Ok(None);
+        }
+        Ok(Some(NumberFormattingOptions {
+            always_sign: self.parse_ubool()?,
+            use_grouping: self.parse_ubool()?,
+            rounding_mode: self.parse_int8(None)?,
+            minimum_integral_digits: self.parse_int(None)?,
+            maximum_integral_digits: self.parse_int(None)?,
+            minimum_fractional_digits: self.parse_int(None)?,
+            maximum_fractional_digits: self.parse_int(None)?,
+        }))
+    }
+
+    /// Parses a bool stored as u32, which is how Unreal stores bools outside of properties
+    fn parse_ubool(&mut self) -> Result<bool> {
+        let offset = self.r.position();
+        match self.r.read_u32::<LE>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(Error::new(ErrorKind::InvalidBool(b), offset)),
+        }
+    }
+
+    /// Parses the count of elements of an array within the body of a property
+    fn parse_count(&mut self) -> Result<u32> {
+        let offset = self.r.position();
+        let count = self.r.read_u32::<LE>()?;
+        if count > self.options.max_element_count {
+            return Err(Error::new(ErrorKind::TooManyElements(count), offset));
+        }
+        Ok(count)
     }
 
     fn parse_object(&mut self, len: Option<u32>) -> Result<String> {
@@ -138,62 +711,615 @@ impl<R: Read> Deserializer<R> {
         Ok(obj)
     }
 
-    fn visit_type<'de, V: Visitor<'de>>(&mut self, v: V, typ: PropertyType, len: Option<u32>) -> Result<V::Value> {
-        trace!("visit_type: {:?}", typ);
-        match typ {
-            PropertyType::Bool => v.visit_bool(self.parse_bool(len)?),
-            PropertyType::Int => v.visit_i32(self.parse_int(len)?),
-            PropertyType::Qword => v.visit_i64(self.parse_qword(len)?),
-            PropertyType::Float => v.visit_f32(self.parse_float(len)?),
-            PropertyType::Str => v.visit_string(self.parse_str(len)?),
-            PropertyType::Object => v.visit_string(self.parse_str(len)?),
-            PropertyType::Byte => v.visit_enum(self.parse_byte(len)?.1.into_deserializer()),
-            PropertyType::Array => v.visit_seq(ArrayDeserializer { de: self }),
-            PropertyType::Struct => unimplemented!(),
-            PropertyType::Unknown(s) => v.visit_map(MapDeserializer::new(self)),
+    /// Parses the body of a SoftObjectProperty or SoftClassProperty, i.e. an `FSoftObjectPath`
+    fn parse_soft_object_path(&mut self) -> Result<SoftObjectPath> {
+        trace!("parse_soft_object_path");
+        let path = if self.package_version.ue5 >= VER_UE5_FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES {
+            let package_name = self.parse_str(None)?;
+            let asset_name = self.parse_str(None)?;
+            SoftObjectPath::from_package_and_asset_name(package_name, &asset_name, self.parse_str(None)?)
+        } else {
+            SoftObjectPath { asset_path_name: self.parse_str(None)?, sub_path_string: self.parse_str(None)? }
+        };
+        trace!("{}", path);
+        Ok(path)
+    }
+
+    /// Parses the body of a DelegateProperty, i.e. an `FScriptDelegate`
+    fn parse_delegate(&mut self) -> Result<Delegate> {
+        trace!("parse_delegate");
+        let delegate = Delegate { object: self.parse_str(None)?, function_name: self.parse_str(None)? };
+        trace!("{}", delegate);
+        Ok(delegate)
+    }
+
+    /// Parses the body of a MulticastInlineDelegateProperty or MulticastSparseDelegateProperty,
+    /// which are both stored as `FMulticastScriptDelegate`
+    fn parse_multicast_delegate(&mut self) -> Result<MulticastDelegate> {
+        trace!("parse_multicast_delegate");
+        let count = self.parse_count()?;
+        let mut delegates = Vec::new();
+        for _ in 0..count {
+            delegates.push(self.parse_delegate()?);
+        }
+        Ok(MulticastDelegate(delegates))
+    }
+
+    /// Parses the body of a FieldPathProperty, i.e. an `FFieldPath`
+    fn parse_field_path(&mut self) -> Result<FieldPath> {
+        trace!("parse_field_path");
+        let count = self.parse_count()?;
+        let mut path = Vec::new();
+        for _ in 0..count {
+            path.push(self.parse_str(None)?);
+        }
+        let field_path = FieldPath { path, owner: self.parse_str(None)? };
+        trace!("{:?}", field_path);
+        Ok(field_path)
+    }
+
+    /// Parses the count of an ArrayProperty and the tag shared by its elements
+    ///
+    /// Elements don't have a tag of their own, except that arrays of structs have a single
+    /// tag preceding all elements from version 500 onwards, whose length is that of all
+    /// elements together. Arrays of bytes contain either raw bytes or enum variants, which is
+    /// told apart by the length of the array.
+    ///
+    /// Returns the tag of the elements, their count and their total length if known.
+    fn parse_array_header(&mut self, inner: Option<PropertyType>, len: Option<u32>) -> Result<(PropertyTag, u32, Option<u32>)> {
+        trace!("parse_array_header: {:?}, {:?}", inner, len);
+        let inner = match inner {
+            Some(inner) => inner,
+            None => self.parse_type_in_array()?,
+        };
+        let count = self.parse_count()?;
+        trace!("{}", count);
+        let mut element = PropertyTag::new(inner, None);
+        let mut elements_len = None;
+        if element.typ == PropertyType::Struct && self.package_version.ue4 >= VER_UE4_INNER_ARRAY_TAG_INFO {
+            let _name = self.parse_str(None)?;
+            let inner_tag = self.parse_type()?;
+            trace!("{:?}, {:?}", _name, inner_tag);
+            element.struct_name = inner_tag.struct_name;
+            element.struct_guid = inner_tag.struct_guid;
+            elements_len = inner_tag.len;
+        } else if element.typ == PropertyType::Struct {
+            element.struct_name = self.struct_type(None);
+        }
+        if element.typ == PropertyType::Byte && len.map(u64::from) == Some(u64::from(count) + 4) {
+            element.enum_name = Some("None".to_string());
+        }
+        Ok((element, count, elements_len))
+    }
+
+    /// Parses the keys removed from a MapProperty and the count of its entries
+    ///
+    /// Returns the tags of the keys and values, which don't have tags of their own.
+    /// Structs as keys or values don't have a struct type name and are parsed as list of properties.
+    fn parse_map_header(&mut self, key_type: Option<PropertyType>, value_type: Option<PropertyType>) -> Result<(PropertyTag, PropertyTag, Vec<Value>, u32)> {
+        trace!("parse_map_header: {:?}, {:?}", key_type, value_type);
+        let key_type = match key_type {
+            Some(key_type) => key_type,
+            None => self.parse_type_in_array()?,
+        };
+        let value_type = match value_type {
+            Some(value_type) => value_type,
+            None => self.parse_type_in_array()?,
+        };
+        let mut key = PropertyTag::new(key_type, None);
+        let mut value = PropertyTag::new(value_type, None);
+        if key.typ == PropertyType::Struct {
+            key.struct_name = self.struct_type(Some("Key"));
+        }
+        if value.typ == PropertyType::Struct {
+            value.struct_name = self.struct_type(Some("Value"));
+        }
+        let (removed, count) = self.parse_removed(&key)?;
+        Ok((key, value, removed, count))
+    }
+
+    /// Parses the elements removed from a SetProperty and the count of its elements
+    ///
+    /// Returns the tag of the elements, which don't have tags of their own like the keys of maps.
+    fn parse_set_header(&mut self, inner: Option<PropertyType>) -> Result<(PropertyTag, Vec<Value>, u32)> {
+        trace!("parse_set_header: {:?}", inner);
+        let inner = match inner {
+            Some(inner) => inner,
+            None => self.parse_type_in_array()?,
+        };
+        let mut element = PropertyTag::new(inner, None);
+        if element.typ == PropertyType::Struct {
+            element.struct_name = self.struct_type(None);
+        }
+        let (removed, count) = self.parse_removed(&element)?;
+        Ok((element, removed, count))
+    }
+
+    /// Parses the removed keys or elements of a MapProperty or SetProperty, followed by the
+    /// count of the remaining ones
+    fn parse_removed(&mut self, tag: &PropertyTag) -> Result<(Vec<Value>, u32)> {
+        let removed_count = self.parse_count()?;
+        trace!("{}", removed_count);
+        let mut removed = Vec::new();
+        for _ in 0..removed_count {
+            removed.push(self.parse_value(tag.clone())?);
+        }
+        let count = self.parse_count()?;
+        trace!("{}", count);
+        Ok((removed, count))
+    }
+
+    /// Parses a list of properties terminated by `None`
+    fn parse_properties(&mut self) -> Result<Vec<Property>> {
+        trace!("parse_properties");
+        let mut properties = Vec::new();
+        loop {
+            let name = self.parse_str(None)?;
+            if name == "None" {
+                return Ok(properties);
+            }
+            let tag = self.parse_type().map_err(|e| e.in_property(&name).at_offset(self.r.position()))?;
+            let array_index = tag.array_index;
+            let guid = tag.guid;
+            let typ = tag.typ.clone();
+            let (start, len) = (self.r.position(), tag.len);
+            let value = self.within(&name, |de| de.parse_value(tag))
+                .and_then(|value| self.check_size(start, len).map(|()| value))
+                .map_err(|e| e.of_type(typ.as_str()).in_property(&name).at_offset(self.r.position()))?;
+            properties.push(Property { name, array_index, guid, value });
+        }
+    }
+
+    /// Parses the body of a property into a `Value`, keeping all of its type information
+    fn parse_value(&mut self, tag: PropertyTag) -> Result<Value> {
+        trace!("parse_value: {:?}", tag);
+        let len = tag.len;
+        if let Some(len) = len.filter(|_| tag.is_unknown()) {
+            // there's no telling how to parse types we don't know, so their body is kept as it is
+            return Ok(Value::Unknown {
+                type_name: tag.typ.as_str().to_string(),
+                inner_type: tag.inner_type.as_ref().map(|typ| typ.as_str().to_string()),
+                value_type: tag.value_type.as_ref().map(|typ| typ.as_str().to_string()),
+                bytes: self.parse_bytes(len)?,
+            });
+        }
+        Ok(match tag.typ {
+            PropertyType::Bool => match (tag.bool_value, len) {
+                // the value of a BoolProperty is part of its tag, so its body is empty
+                (Some(_), Some(len)) if len != 0 =>
+                    return Err(Error::new(ErrorKind::InvalidBoolLength(len), self.r.position())),
+                (Some(b), _) => Value::Bool(b),
+                (None, _) => Value::Bool(self.parse_bool(len)?),
+            },
+            PropertyType::Int8 => Value::Int8(self.parse_int8(len)?),
+            PropertyType::Int16 => Value::Int16(self.parse_int16(len)?),
+            PropertyType::Int => Value::Int(self.parse_int(len)?),
+            PropertyType::Int64 => Value::Int64(self.parse_int64(len)?),
+            PropertyType::UInt16 => Value::UInt16(self.parse_uint16(len)?),
+            PropertyType::UInt32 => Value::UInt32(self.parse_uint32(len)?),
+            PropertyType::UInt64 => Value::UInt64(self.parse_uint64(len)?),
+            PropertyType::Qword => Value::QWord(self.parse_qword(len)?),
+            PropertyType::Float => Value::Float(self.parse_float(len)?),
+            PropertyType::Double => Value::Double(self.parse_double(len)?),
+            PropertyType::Str => Value::Str(self.parse_str(len)?),
+            PropertyType::Text => Value::Text(self.parse_text()?),
+            PropertyType::Name => Value::Name(self.parse_name(len)?),
+            PropertyType::Object => Value::Object(self.parse_object(len)?),
+            PropertyType::SoftObject => Value::SoftObject(self.parse_soft_object_path()?),
+            PropertyType::SoftClass => Value::SoftClass(self.parse_soft_object_path()?),
+            PropertyType::LazyObject => Value::LazyObject(self.parse_guid()?),
+            PropertyType::Interface => Value::Interface(self.parse_object(len)?),
+            PropertyType::Delegate => Value::Delegate(self.parse_delegate()?),
+            PropertyType::MulticastInlineDelegate => Value::MulticastInlineDelegate(self.parse_multicast_delegate()?),
+            PropertyType::MulticastSparseDelegate => Value::MulticastSparseDelegate(self.parse_multicast_delegate()?),
+            PropertyType::FieldPath => Value::FieldPath(self.parse_field_path()?),
+            PropertyType::Byte if tag.is_raw_byte() => Value::UInt8(self.parse_byte(len)?),
+            PropertyType::Byte => {
+                let variant = self.parse_byte_variant(len)?;
+                Value::Byte { enum_name: enum_name(tag.enum_name, &variant), variant }
+            }
+            PropertyType::Enum => {
+                let variant = self.parse_enum(len)?;
+                Value::Enum { enum_name: enum_name(tag.enum_name, &variant), variant }
+            }
+            PropertyType::Array => {
+                let (element, count, elements_len) = self.parse_array_header(tag.inner_type, tag.len)?;
+                // the struct type is only present in front of arrays of structs in newer versions
+                let struct_type = element.struct_name.clone()
+                    .filter(|_| self.package_version.ue4 >= VER_UE4_INNER_ARRAY_TAG_INFO)
+                    .map(|name| StructType { name, guid: element.struct_guid });
+                let start = self.r.position();
+                let mut elements = Vec::new();
+                for i in 0..count as usize {
+                    elements.push(self.parse_element(&element, i)?);
+                }
+                self.check_size(start, elements_len)?;
+                Value::Array { inner_type: element.typ.as_str().to_string(), struct_type, elements }
+            }
+            PropertyType::Map => {
+                let (key, value, removed, count) = self.parse_map_header(tag.inner_type, tag.value_type)?;
+                let mut entries = Vec::new();
+                for i in 0..count as usize {
+                    let k = self.parse_element(&key, i)?;
+                    let v = self.parse_element(&value, i)?;
+                    entries.push((k, v));
+                }
+                Value::Map {
+                    key_type: key.typ.as_str().to_string(),
+                    value_type: value.typ.as_str().to_string(),
+                    removed,
+                    entries,
+                }
+            }
+            PropertyType::Set => {
+                let (element, removed, count) = self.parse_set_header(tag.inner_type)?;
+                let mut elements = Vec::new();
+                for i in 0..count as usize {
+                    elements.push(self.parse_element(&element, i)?);
+                }
+                Value::Set { inner_type: element.typ.as_str().to_string(), removed, elements }
+            }
+            PropertyType::Struct => {
+                // the struct name of array elements is unknown before version 500
+                let type_name = tag.struct_name.unwrap_or_default();
+                let properties = match self.parse_native_struct(&type_name, len)? {
+                    Some(fields) => fields,
+                    None => self.nested(Self::parse_properties)?,
+                };
+                Value::Struct { type_name, guid: tag.struct_guid, properties }
+            }
+            PropertyType::Unknown(typ) => return Err(Error::new(ErrorKind::UnknownPropertyType(typ), self.r.position())),
+        })
+    }
+
+    /// Parses an element of an array or set or a key or value of a map, adding its index to errors
+    fn parse_element(&mut self, element: &PropertyTag, index: usize) -> Result<Value> {
+        self.parse_value(element.clone())
+            .map_err(|e| e.of_type(element.typ.as_str()).in_element(index).at_offset(self.r.position()))
+    }
+
+    /// Visits the body of a property
+    ///
+    /// Arrays, maps, sets and structs are visited while they are parsed, all other types are parsed
+    /// into a `Value` first.
+    fn visit_type<'de, V: Visitor<'de>>(&mut self, v: V, tag: PropertyTag) -> Result<V::Value> {
+        trace!("visit_type: {:?}", tag);
+        if let Some(len) = tag.len.filter(|_| tag.is_unknown()) {
+            // there's no telling how to parse types we don't know, so they can only be skipped
+            self.skip(len)?;
+            return v.visit_unit();
+        }
+        match tag.typ {
+            PropertyType::Array => self.visit_array(v, tag, false),
+            PropertyType::Map => {
+                // removed keys are only relevant when loading into an existing object
+                let (key, value, _removed, count) = self.parse_map_header(tag.inner_type, tag.value_type)?;
+                v.visit_map(MapEntriesDeserializer { de: self, key, value, index: 0, remaining: count })
+            }
+            PropertyType::Set => {
+                let (element, _removed, count) = self.parse_set_header(tag.inner_type)?;
+                v.visit_seq(ArrayDeserializer::new(self, element, count))
+            }
+            PropertyType::Struct => self.visit_struct(v, tag),
+            _ => serde::Deserializer::deserialize_any(self.parse_value(tag)?, v),
+        }
+    }
+
+    /// Visits the body of an ArrayProperty
+    ///
+    /// Raw bytes are read at once and visited as byte buffer if the visitor asked for bytes.
+    fn visit_array<'de, V: Visitor<'de>>(&mut self, v: V, tag: PropertyTag, bytes: bool) -> Result<V::Value> {
+        let (element, count, elements_len) = self.parse_array_header(tag.inner_type, tag.len)?;
+        if !element.is_raw_byte() {
+            return v.visit_seq(ArrayDeserializer::new(self, element, count).with_len(elements_len));
+        }
+        let buf = self.parse_bytes(count)?;
+        if bytes {
+            v.visit_byte_buf(buf)
+        } else {
+            v.visit_seq(SeqDeserializer::new(buf.into_iter()))
+        }
+    }
+
+    /// Visits a property as bytes, which only arrays of raw bytes can be visited as
+    fn visit_bytes<'de, V: Visitor<'de>>(&mut self, v: V, tag: PropertyTag) -> Result<V::Value> {
+        match tag.typ {
+            PropertyType::Array => self.visit_array(v, tag, true),
+            _ => self.visit_type(v, tag),
+        }
+    }
+
+    /// Visits the body of a StructProperty
+    ///
+    /// Native structs are passed to the visitor as map of their fields. All other structs
+    /// are a list of properties terminated by `None`, which is visited as map.
+    fn visit_struct<'de, V: Visitor<'de>>(&mut self, v: V, tag: PropertyTag) -> Result<V::Value> {
+        trace!("visit_struct: {:?}", tag.struct_name);
+        let native = tag.struct_name.as_deref()
+            .is_some_and(|name| self.options.struct_codecs.contains(name) || native_struct_fields(name).is_some());
+        if native {
+            serde::Deserializer::deserialize_any(self.parse_value(tag)?, v)
+        } else {
+            self.nested(|de| v.visit_map(MapDeserializer::new(de)))
+        }
+    }
+
+    /// Skips the body of a property, which is parsed if its length is unknown, i.e. for array elements
+    ///
+    /// The visitor only gets to see a unit, as `IgnoredAny` can't visit enums.
+    fn ignore_type<'de, V: Visitor<'de>>(&mut self, v: V, tag: PropertyTag) -> Result<V::Value> {
+        match tag.len {
+            Some(len) => self.skip(len)?,
+            None => { self.parse_value(tag)?; },
+        }
+        v.visit_unit()
+    }
+
+    /// Visits a property as newtype struct, which is how types like `FText` and `SoftObjectPath`
+    /// ask for all fields of their property instead of its string
+    fn visit_newtype<'de, V: Visitor<'de>>(&mut self, name: &'static str, v: V, tag: PropertyTag) -> Result<V::Value> {
+        let typed = match tag.typ {
+            PropertyType::Text => name == TEXT_TOKEN,
+            PropertyType::SoftObject | PropertyType::SoftClass => name == SOFT_OBJECT_PATH_TOKEN,
+            PropertyType::Delegate => name == DELEGATE_TOKEN,
+            PropertyType::MulticastInlineDelegate | PropertyType::MulticastSparseDelegate => name == MULTICAST_DELEGATE_TOKEN,
+            PropertyType::FieldPath => name == FIELD_PATH_TOKEN,
+            _ => false,
+        };
+        if typed {
+            serde::Deserializer::deserialize_newtype_struct(self.parse_value(tag)?, name, v)
+        } else {
+            self.visit_type(v, tag)
+        }
+    }
+
+    /// Visits a property either as `Value` or as whatever the visitor expects
+    fn visit_type_or_value<'de, V: Visitor<'de>>(&mut self, name: &'static str, variants: &'static [&'static str], v: V, tag: PropertyTag) -> Result<V::Value> {
+        if name == VALUE_TOKEN {
+            serde::Deserializer::deserialize_enum(self.parse_value(tag)?, name, variants, v)
+        } else {
+            self.visit_type(v, tag)
         }
     }
 }
 
-impl<'a, 'de, R: Read> serde::Deserializer<'de> for &'a mut Deserializer<R> {
+impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
     type Error = Error;
 
     fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         trace!("deserialize_any");
-        // TODO: in_array
-        let (typ, len) = self.parse_type()?;
-        let len = Some(len);
+        let tag = self.parse_type()?;
+        self.visit_type(v, tag)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        trace!("deserialize_enum: {:?}", name);
+        let tag = self.parse_type()?;
+        self.visit_type_or_value(name, variants, v, tag)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, v: V) -> Result<V::Value> {
+        trace!("deserialize_newtype_struct: {:?}", name);
+        let tag = self.parse_type()?;
+        self.visit_newtype(name, v, tag)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_ignored_any");
+        let tag = self.parse_type()?;
+        self.ignore_type(v, tag)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_bytes");
+        let tag = self.parse_type()?;
+        self.visit_bytes(v, tag)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        self.deserialize_bytes(v)
+    }
 
-        self.visit_type(v, typ, len)
+    /// Missing properties are `None`, so everything that is present is `Some`
+    fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        v.visit_some(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+/// Deserializer for the save game object, i.e. the properties following the header
+struct RootDeserializer<'a, T: Read + 'a> {
+    de: &'a mut Deserializer<T>,
+    /// Struct type name of the save game object when deserialized as `Value`
+    class_name: &'a str,
+}
+
+impl<'a, 'de, T: Read + 'a> serde::Deserializer<'de> for RootDeserializer<'a, T> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_any (root)");
+        v.visit_map(MapDeserializer::new(self.de))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        trace!("deserialize_enum (root): {:?}", name);
+        if name != VALUE_TOKEN {
+            return self.deserialize_any(v);
+        }
+        let properties = self.de.parse_properties()?;
+        let value = Value::Struct { type_name: self.class_name.to_string(), guid: Guid::default(), properties };
+        serde::Deserializer::deserialize_enum(value, name, variants, v)
     }
 
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf option unit unit_struct newtype_struct seq tuple
-        tuple_struct map struct enum identifier ignored_any
+        tuple_struct map struct identifier ignored_any
     }
 }
 
 struct ArrayDeserializer<'a, T: Read + 'a> {
     de: &'a mut Deserializer<T>,
+    /// Tag shared by all elements
+    element: PropertyTag,
+    index: usize,
+    remaining: u32,
+    /// Offset of the first element
+    start: usize,
+    /// Length of all elements, if known
+    len: Option<u32>,
+}
+
+impl<'a, T: Read + 'a> ArrayDeserializer<'a, T> {
+    fn new(de: &'a mut Deserializer<T>, element: PropertyTag, count: u32) -> ArrayDeserializer<'a, T> {
+        let start = de.r.position();
+        ArrayDeserializer { de, element, index: 0, remaining: count, start, len: None }
+    }
+
+    /// Checks that the elements have the given total length once all of them are parsed
+    fn with_len(mut self, len: Option<u32>) -> ArrayDeserializer<'a, T> {
+        self.len = len;
+        self
+    }
 }
 
 impl<'de, 'a, T: Read> SeqAccess<'de> for ArrayDeserializer<'a, T> {
     type Error = Error;
 
     fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>> {
-        let typ = self.de.parse_type_in_array()?;
-        unimplemented!()
+        trace!("next_element_seed");
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.index += 1;
+        let element = seed.deserialize(ElementDeserializer { de: &mut *self.de, element: &self.element })
+            .map_err(|e| e.of_type(self.element.typ.as_str()).in_element(self.index - 1).at_offset(self.de.r.position()))?;
+        if self.remaining == 0 {
+            self.de.check_size(self.start, self.len)?;
+        }
+        Ok(Some(element))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining as usize)
+    }
+}
+
+/// Deserializer for a single array element or the body of a property, whose tag is known beforehand
+struct ElementDeserializer<'a, T: Read + 'a> {
+    de: &'a mut Deserializer<T>,
+    element: &'a PropertyTag,
+}
+
+impl<'a, 'de, T: Read + 'a> serde::Deserializer<'de> for ElementDeserializer<'a, T> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_any (element)");
+        self.de.visit_type(v, self.element.clone())
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        trace!("deserialize_enum (element): {:?}", name);
+        self.de.visit_type_or_value(name, variants, v, self.element.clone())
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, v: V) -> Result<V::Value> {
+        trace!("deserialize_newtype_struct (element): {:?}", name);
+        self.de.visit_newtype(name, v, self.element.clone())
+    }
+
+    /// Structs in maps, sets and old arrays don't have a struct type, so the Rust type tells
+    /// whether they are native structs
+    fn deserialize_struct<V: Visitor<'de>>(self, name: &'static str, _fields: &'static [&'static str], v: V) -> Result<V::Value> {
+        trace!("deserialize_struct (element): {:?}", name);
+        let native = self.de.options.struct_codecs.contains(name) || native_struct_fields(name).is_some();
+        if self.element.typ == PropertyType::Struct && self.element.struct_name.is_none() && native {
+            let mut element = self.element.clone();
+            element.struct_name = Some(name.to_string());
+            return self.de.visit_type(v, element);
+        }
+        self.de.visit_type(v, self.element.clone())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_ignored_any (element)");
+        self.de.ignore_type(v, self.element.clone())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        trace!("deserialize_bytes (element)");
+        self.de.visit_bytes(v, self.element.clone())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        self.deserialize_bytes(v)
+    }
+
+    /// Missing properties are `None`, so everything that is present is `Some`
+    fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        v.visit_some(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple
+        tuple_struct map identifier
+    }
+}
+
+/// Accesses the entries of a MapProperty, whose keys and values don't have tags of their own
+struct MapEntriesDeserializer<'a, T: Read + 'a> {
+    de: &'a mut Deserializer<T>,
+    key: PropertyTag,
+    value: PropertyTag,
+    /// Index of the next key
+    index: usize,
+    remaining: u32,
+}
+
+impl<'a, 'de, T: Read + 'a> MapAccess<'de> for MapEntriesDeserializer<'a, T> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        trace!("next_key_seed (map entry)");
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.index += 1;
+        seed.deserialize(ElementDeserializer { de: &mut *self.de, element: &self.key })
+            .map_err(|e| e.of_type(self.key.typ.as_str()).in_element(self.index - 1).at_offset(self.de.r.position()))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        trace!("next_value_seed (map entry)");
+        seed.deserialize(ElementDeserializer { de: &mut *self.de, element: &self.value })
+            .map_err(|e| e.of_type(self.value.typ.as_str()).in_element(self.index.saturating_sub(1)).at_offset(self.de.r.position()))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining as usize)
     }
 }
 
 pub struct MapDeserializer<'a, T: Read + 'a> {
     de: &'a mut Deserializer<T>,
+    /// Name of the property whose value is visited next
+    name: String,
 }
 
 impl<'a, T: Read + 'a> MapDeserializer<'a, T> {
     pub fn new(de: &'a mut Deserializer<T>) -> MapDeserializer<'a, T> {
-        MapDeserializer { de }
+        MapDeserializer { de, name: String::new() }
     }
 }
 
@@ -206,55 +1332,166 @@ impl<'a, 'de, T: Read + 'a> MapAccess<'de> for MapDeserializer<'a, T> {
         if key == "None" {
             return Ok(None);
         }
-        seed.deserialize(key.into_deserializer()).map(Some)
+        self.name.clone_from(&key);
+        seed.deserialize(key.into_deserializer())
+            .map_err(|e: Error| e.in_property(&self.name).at_offset(self.de.r.position()))
+            .map(Some)
     }
 
     fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
         trace!("next_value_seed");
-        seed.deserialize(&mut *self.de)
+        let tag = self.de.parse_type().map_err(|e| e.in_property(&self.name).at_offset(self.de.r.position()))?;
+        let start = self.de.r.position();
+        let name = &self.name;
+        self.de.within(name, |de| seed.deserialize(ElementDeserializer { de, element: &tag }))
+            .and_then(|value| self.de.check_size(start, tag.len).map(|()| value))
+            .map_err(|e| e.of_type(tag.typ.as_str()).in_property(&self.name).at_offset(self.de.r.position()))
     }
 }
 
-fn parse_string<R: Read>(mut r: R, read: usize) -> Result<(String, usize)> {
+/// Parses the body of a property written by the Serializer back into a `Value`
+///
+/// This is how fields of structs with codecs are passed to their encoder.
+pub(crate) fn parse_value_from(body: &[u8], tag: PropertyTag, package_version: PackageVersion, struct_codecs: &StructCodecRegistry) -> Result<Value> {
+    let options = DeserializerOptions { struct_codecs: struct_codecs.clone(), ..DeserializerOptions::default() };
+    let mut de = Deserializer { r: CountingReader::new(body, 0), package_version, options, depth: 0, path: Vec::new() };
+    de.parse_value(tag)
+}
+
+/// Enum name of a ByteProperty or EnumProperty with the given variant
+///
+/// Elements of arrays, sets and maps don't have an enum name in their tag, it's taken from the
+/// `EnumName::` prefix of their variant instead.
+fn enum_name(tag_enum_name: Option<String>, variant: &str) -> String {
+    tag_enum_name.unwrap_or_else(|| match variant.find("::") {
+        Some(end) => variant[..end].to_string(),
+        None => "None".to_string(),
+    })
+}
+
+/// Parses a string, which is its length followed by zero terminated Windows-1252 or UTF-16 chars
+///
+/// The maximum length is in bytes and checked before the string is read.
+pub(crate) fn parse_string<R: Read>(r: &mut CountingReader<R>, max_len: usize) -> Result<String> {
+    let start = r.position();
     let len = r.read_i32::<LE>()?;
-    // positive len: WINDOWS_1252, negative len: UTF_16LE
+    let byte_len = if len < 0 { len.unsigned_abs() as usize * 2 } else { len as usize };
+    if byte_len > max_len {
+        return Err(Error::new(ErrorKind::StringTooLong(byte_len), start));
+    }
+    // positive len: WINDOWS_1252, negative len: UTF_16LE, zero len: empty string without terminator
     if len == 0 {
-        Err(Error::new(ErrorKind::InvalidStringLength(0), read))
-    } else if len > 0 {
-        let mut buf = vec![0u8; len as usize];
-        r.read_exact(&mut buf)?;
-        let null_byte = buf.pop().unwrap();
-        if null_byte != 0 {
-            buf.push(null_byte);
-            return Err(Error::new(ErrorKind::StringNotZeroTerminated(buf), read));
-        }
-        let s = WINDOWS_1252.decode_without_bom_handling(&buf).0.into_owned();
-        Ok((s, buf.len() + 4))
+        return Ok(String::new());
+    }
+    // not preallocated, as the length may be garbage
+    let mut buf = Vec::new();
+    (&mut *r).take(byte_len as u64).read_to_end(&mut buf)?;
+    if buf.len() != byte_len {
+        return Err(Error::new(ErrorKind::UnexpectedEof, r.position()));
+    }
+    if len > 0 {
+        if buf.last() != Some(&0) {
+            return Err(Error::new(ErrorKind::StringNotZeroTerminated(buf), start));
+        }
+        buf.pop();
+        Ok(WINDOWS_1252.decode_without_bom_handling(&buf).0.into_owned())
     } else {
-        let mut buf = vec![0u8; len.abs() as usize * 2];
-        r.read_exact(&mut buf)?;
-        let last = buf.pop().unwrap();
-        let second_last = buf.pop().unwrap();
-        if last != 0 && second_last != 0 {
-            buf.push(last);
-            buf.push(second_last);
-            return Err(Error::new(ErrorKind::StringNotZeroTerminated(buf), read));
-        }
-        let s = UTF_16LE.decode_without_bom_handling(&buf).0.into_owned();
-        Ok((s, buf.len() + 4))
+        if !buf.ends_with(&[0, 0]) {
+            return Err(Error::new(ErrorKind::StringNotZeroTerminated(buf), start));
+        }
+        buf.truncate(byte_len - 2);
+        let s = UTF_16LE.decode_without_bom_handling_and_without_replacement(&buf).map(|s| s.into_owned());
+        s.ok_or_else(|| Error::new(ErrorKind::InvalidUtf16(buf), start))
+    }
+}
+
+/// Tag of a property, containing its type and type-specific information
+///
+/// Array elements don't have a tag of their own. They are visited with a tag without
+/// length, which contains information shared by all elements if present.
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyTag {
+    pub(crate) typ: PropertyType,
+    pub(crate) len: Option<u32>,
+    /// Index of the element if the property is an element of a static array
+    pub(crate) array_index: u32,
+    /// Optional GUID of the property
+    pub(crate) guid: Option<Guid>,
+    /// Struct type name of a StructProperty
+    pub(crate) struct_name: Option<String>,
+    /// GUID of the struct type of a StructProperty
+    pub(crate) struct_guid: Guid,
+    /// Value of a BoolProperty, which is stored in the tag
+    pub(crate) bool_value: Option<bool>,
+    /// Enum name of a ByteProperty or EnumProperty
+    ///
+    /// ByteProperties holding raw bytes have the enum name `None`, which is also set for the
+    /// elements of arrays of raw bytes.
+    pub(crate) enum_name: Option<String>,
+    /// Element type of an ArrayProperty or key type of a MapProperty
+    pub(crate) inner_type: Option<PropertyType>,
+    /// Value type of a MapProperty
+    pub(crate) value_type: Option<PropertyType>,
+}
+
+impl PropertyTag {
+    pub(crate) fn new(typ: PropertyType, len: Option<u32>) -> PropertyTag {
+        PropertyTag {
+            typ,
+            len,
+            array_index: 0,
+            guid: None,
+            struct_name: None,
+            struct_guid: Guid::default(),
+            bool_value: None,
+            enum_name: None,
+            inner_type: None,
+            value_type: None,
+        }
+    }
+
+    /// Whether the type or the type of the elements, keys or values is unknown
+    pub(crate) fn is_unknown(&self) -> bool {
+        let unknown = |typ: &PropertyType| matches!(typ, PropertyType::Unknown(_));
+        unknown(&self.typ) || self.inner_type.as_ref().is_some_and(unknown) || self.value_type.as_ref().is_some_and(unknown)
+    }
+
+    /// Whether this is a ByteProperty holding a raw byte instead of an enum variant
+    pub(crate) fn is_raw_byte(&self) -> bool {
+        self.typ == PropertyType::Byte && self.enum_name.as_deref() == Some("None")
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum PropertyType {
+pub(crate) enum PropertyType {
     Bool,
+    Int8,
+    Int16,
     Int,
+    Int64,
+    UInt16,
+    UInt32,
+    UInt64,
     Qword,
     Float,
+    Double,
     Str,
+    Text,
+    Name,
     Object,
+    SoftObject,
+    SoftClass,
+    LazyObject,
+    Interface,
+    Delegate,
+    MulticastInlineDelegate,
+    MulticastSparseDelegate,
+    FieldPath,
     Byte,
+    Enum,
     Array,
+    Map,
+    Set,
     Struct,
     // i have no idea, what i'm doing
 //    LinearColor,
@@ -268,13 +1505,33 @@ impl FromStr for PropertyType {
     fn from_str(s: &str) -> ::std::result::Result<Self, Void> {
         Ok(match s {
             "BoolProperty" => PropertyType::Bool,
+            "Int8Property" => PropertyType::Int8,
+            "Int16Property" => PropertyType::Int16,
             "IntProperty" => PropertyType::Int,
+            "Int64Property" => PropertyType::Int64,
+            "UInt16Property" => PropertyType::UInt16,
+            "UInt32Property" => PropertyType::UInt32,
+            "UInt64Property" => PropertyType::UInt64,
             "QWordProperty" => PropertyType::Qword,
             "FloatProperty" => PropertyType::Float,
+            "DoubleProperty" => PropertyType::Double,
             "StrProperty" => PropertyType::Str,
+            "TextProperty" => PropertyType::Text,
+            "NameProperty" => PropertyType::Name,
             "ObjectProperty" => PropertyType::Object,
+            "SoftObjectProperty" => PropertyType::SoftObject,
+            "SoftClassProperty" => PropertyType::SoftClass,
+            "LazyObjectProperty" => PropertyType::LazyObject,
+            "InterfaceProperty" => PropertyType::Interface,
+            "DelegateProperty" => PropertyType::Delegate,
+            "MulticastInlineDelegateProperty" => PropertyType::MulticastInlineDelegate,
+            "MulticastSparseDelegateProperty" => PropertyType::MulticastSparseDelegate,
+            "FieldPathProperty" => PropertyType::FieldPath,
             "ByteProperty" => PropertyType::Byte,
+            "EnumProperty" => PropertyType::Enum,
             "ArrayProperty" => PropertyType::Array,
+            "MapProperty" => PropertyType::Map,
+            "SetProperty" => PropertyType::Set,
             "StructProperty" => PropertyType::Struct,
             // I have no idea what I'm doing
 //            "LinearColor" => PropertyType::LinearColor,
@@ -284,3 +1541,41 @@ impl FromStr for PropertyType {
         })
     }
 }
+
+impl PropertyType {
+    /// Name of the type as used in tags
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            PropertyType::Bool => "BoolProperty",
+            PropertyType::Int8 => "Int8Property",
+            PropertyType::Int16 => "Int16Property",
+            PropertyType::Int => "IntProperty",
+            PropertyType::Int64 => "Int64Property",
+            PropertyType::UInt16 => "UInt16Property",
+            PropertyType::UInt32 => "UInt32Property",
+            PropertyType::UInt64 => "UInt64Property",
+            PropertyType::Qword => "QWordProperty",
+            PropertyType::Float => "FloatProperty",
+            PropertyType::Double => "DoubleProperty",
+            PropertyType::Str => "StrProperty",
+            PropertyType::Text => "TextProperty",
+            PropertyType::Name => "NameProperty",
+            PropertyType::Object => "ObjectProperty",
+            PropertyType::SoftObject => "SoftObjectProperty",
+            PropertyType::SoftClass => "SoftClassProperty",
+            PropertyType::LazyObject => "LazyObjectProperty",
+            PropertyType::Interface => "InterfaceProperty",
+            PropertyType::Delegate => "DelegateProperty",
+            PropertyType::MulticastInlineDelegate => "MulticastInlineDelegateProperty",
+            PropertyType::MulticastSparseDelegate => "MulticastSparseDelegateProperty",
+            PropertyType::FieldPath => "FieldPathProperty",
+            PropertyType::Byte => "ByteProperty",
+            PropertyType::Enum => "EnumProperty",
+            PropertyType::Array => "ArrayProperty",
+            PropertyType::Map => "MapProperty",
+            PropertyType::Set => "SetProperty",
+            PropertyType::Struct => "StructProperty",
+            PropertyType::Unknown(s) => s,
+        }
+    }
+}
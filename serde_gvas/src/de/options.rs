@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use struct_codec::StructCodecRegistry;
+
+/// Options of the Deserializer, most notably limits protecting against malicious or corrupted files
+///
+/// Exceeding a limit fails with a dedicated `ErrorKind` instead of allocating huge buffers or
+/// overflowing the stack. The default limits are far beyond what save games need.
+///
+/// ```
+/// use serde_gvas::{DeserializerOptions, Value, from_reader_with_options};
+///
+/// let options = DeserializerOptions { max_total_bytes: 16 << 20, ..DeserializerOptions::default() };
+/// let bytes = std::fs::read("../files/flai.sav").unwrap();
+/// let (_header, value): (_, Value) = from_reader_with_options(&bytes[..], &options).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeserializerOptions {
+    /// Maximum length of a string in bytes, including its terminator
+    pub max_string_length: usize,
+    /// Maximum count of the elements of an array, set or map, or of any other list
+    pub max_element_count: u32,
+    /// Maximum nesting depth of structs and texts, which are the only types containing themselves
+    ///
+    /// Each level takes some kilobytes of stack when deserialized into a `Value`, a lot more in
+    /// debug builds, so deeper nesting may need a thread with a larger stack.
+    pub max_depth: usize,
+    /// Maximum size of the file, including its header and trailing bytes
+    pub max_total_bytes: usize,
+    /// Codecs of structs whose body is binary
+    pub struct_codecs: StructCodecRegistry,
+    /// Struct type names of the structs in maps, sets and arrays, by the path of their property
+    ///
+    /// Keys and values of maps, elements of sets and, before version 500, elements of arrays
+    /// don't have a struct type name, so native structs and structs with a codec among them can't
+    /// be told apart from other structs. Deserializing into a Rust type uses the name of the type
+    /// instead, `Value` needs it from here.
+    ///
+    /// The path consists of the names of the properties from the save game object to the map,
+    /// set or array joined by `.`, followed by `.Key` or `.Value` for maps, e.g.
+    /// `Character.Positions.Value` for the `Vector` values of the `Positions` map in the
+    /// `Character` struct.
+    pub struct_types: HashMap<String, String>,
+}
+
+impl Default for DeserializerOptions {
+    fn default() -> DeserializerOptions {
+        DeserializerOptions {
+            max_string_length: 16 << 20,
+            max_element_count: 16 << 20,
+            max_depth: 64,
+            max_total_bytes: usize::MAX,
+            struct_codecs: StructCodecRegistry::default(),
+            struct_types: HashMap::new(),
+        }
+    }
+}
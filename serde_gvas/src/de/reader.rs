@@ -0,0 +1,61 @@
+use std::error;
+use std::fmt;
+use std::io::{self, Read};
+
+/// Reader keeping track of its position, which is the byte offset reported by errors
+///
+/// The position counts every byte read, no matter whether it was read by a parse function,
+/// a codec or `read_to_end`, so it is the offset in the file if counting starts at its beginning.
+/// Reading past the limit fails with `TotalBytesExceeded`.
+pub(crate) struct CountingReader<R> {
+    inner: R,
+    position: usize,
+    limit: usize,
+}
+
+impl<R: Read> CountingReader<R> {
+    /// Wraps a reader whose next byte is at the given position
+    pub(crate) fn new(inner: R, position: usize) -> CountingReader<R> {
+        CountingReader { inner, position, limit: usize::MAX }
+    }
+
+    /// Limits the position up to which can be read
+    pub(crate) fn with_limit(mut self, limit: usize) -> CountingReader<R> {
+        self.limit = limit;
+        self
+    }
+
+    /// Offset of the next byte to be read
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.limit.saturating_sub(self.position);
+        if remaining == 0 && !buf.is_empty() {
+            // reaching the limit is fine as long as there are no more bytes
+            return match self.inner.read(&mut buf[..1])? {
+                0 => Ok(0),
+                _ => Err(io::Error::other(TotalBytesExceeded(self.limit))),
+            };
+        }
+        let len = buf.len().min(remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// I/O error of a `CountingReader` whose limit is exceeded, turned into `ErrorKind::TooManyBytes`
+#[derive(Debug)]
+pub(crate) struct TotalBytesExceeded(pub(crate) usize);
+
+impl fmt::Display for TotalBytesExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "more than {} bytes", self.0)
+    }
+}
+
+impl error::Error for TotalBytesExceeded {}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct `Delegate` is (de)serialized as
+pub(crate) const DELEGATE_TOKEN: &str = "$serde_gvas::Delegate";
+/// Name of the newtype struct `MulticastDelegate` is (de)serialized as
+pub(crate) const MULTICAST_DELEGATE_TOKEN: &str = "$serde_gvas::MulticastDelegate";
+
+/// Function bound to an object as stored in a DelegateProperty, i.e. Unreal's `FScriptDelegate`
+///
+/// A DelegateProperty can be deserialized into a `String`, which results in the string returned
+/// by `Display`, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor.OnHit`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Delegate {
+    /// Path of the object the function is called on
+    pub object: String,
+    pub function_name: String,
+}
+
+/// Functions bound to objects as stored in a MulticastInlineDelegateProperty or
+/// MulticastSparseDelegateProperty, i.e. Unreal's `FMulticastScriptDelegate`
+///
+/// Such a property can be deserialized into a `Vec<String>` as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MulticastDelegate(pub Vec<Delegate>);
+
+/// Formats the delegate like Unreal does, appending the function name to the object path
+impl fmt::Display for Delegate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.object, self.function_name)
+    }
+}
+
+/// Fields of `Delegate`, which is a newtype struct around them
+#[derive(Serialize, Deserialize)]
+struct DelegateFields<S> {
+    object: S,
+    function_name: S,
+}
+
+impl Serialize for Delegate {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(DELEGATE_TOKEN, &DelegateFields { object: &self.object, function_name: &self.function_name })
+    }
+}
+
+impl<'de> Deserialize<'de> for Delegate {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Delegate, D::Error> {
+        d.deserialize_newtype_struct(DELEGATE_TOKEN, DelegateVisitor)
+    }
+}
+
+struct DelegateVisitor;
+
+impl<'de> Visitor<'de> for DelegateVisitor {
+    type Value = Delegate;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a DelegateProperty")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<Delegate, D::Error> {
+        let fields: DelegateFields<String> = DelegateFields::deserialize(d)?;
+        Ok(Delegate { object: fields.object, function_name: fields.function_name })
+    }
+}
+
+impl Serialize for MulticastDelegate {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(MULTICAST_DELEGATE_TOKEN, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for MulticastDelegate {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<MulticastDelegate, D::Error> {
+        d.deserialize_newtype_struct(MULTICAST_DELEGATE_TOKEN, MulticastDelegateVisitor)
+    }
+}
+
+struct MulticastDelegateVisitor;
+
+impl<'de> Visitor<'de> for MulticastDelegateVisitor {
+    type Value = MulticastDelegate;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a MulticastInlineDelegateProperty or MulticastSparseDelegateProperty")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<MulticastDelegate, D::Error> {
+        Vec::deserialize(d).map(MulticastDelegate)
+    }
+}
@@ -3,24 +3,60 @@ use std::fmt::{self, Display};
 
 use serde::{de, ser};
 
+use de::TotalBytesExceeded;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Error of the Serializer or Deserializer
+///
+/// Errors of the Deserializer know where they occurred: the byte offset, the path of the property
+/// (e.g. `CharacterSlots[3].DNA.HairColor`) and the type of the innermost property being read.
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
-    offset: usize,
+    offset: Option<usize>,
+    /// Property names separated by `.` and element indices in brackets, outermost first
+    path: String,
+    property_type: Option<String>,
 }
 
 #[derive(Debug)]
 pub enum ErrorKind {
     Message(String),
     Io(std::io::Error),
+    UnexpectedEof,
 
+    InvalidMagic([u8; 4]),
+    UnsupportedCustomVersionFormat(u32),
+    UnsupportedType(&'static str),
     StringNotZeroTerminated(Vec<u8>),
+    InvalidUtf16(Vec<u8>),
+    InvalidBool(u32),
     InvalidStringLength(u32),
+    InvalidBoolLength(u32),
+    InvalidByteLength(u32),
+    InvalidInt8Length(u32),
+    InvalidInt16Length(u32),
     InvalidIntLength(u32),
+    InvalidInt64Length(u32),
+    InvalidUInt16Length(u32),
+    InvalidUInt32Length(u32),
+    InvalidUInt64Length(u32),
     InvalidQwordLength(u32),
     InvalidFloatLength(u32),
+    InvalidDoubleLength(u32),
+    UnknownPropertyType(String),
+    /// The body of a property or struct has a different size than declared in its tag
+    SizeMismatch { expected: u32, actual: usize },
+    InvalidGuid(String),
+    InvalidDateTime(String),
+    InvalidTimespan(String),
+    InvalidTextHistory(i8),
+    InvalidFormatArgumentType(i8),
+    StringTooLong(usize),
+    TooManyElements(u32),
+    NestingTooDeep(usize),
+    TooManyBytes(usize),
     // Zero or more variants that can be created directly by the Serializer and
     // Deserializer without going through `ser::Error` and `de::Error`. These
     // are specific to the format, in this case JSON.
@@ -45,20 +81,86 @@ impl Error {
     pub fn new(kind: ErrorKind, offset: usize) -> Error {
         Error {
             kind,
-            offset,
+            offset: Some(offset),
+            path: String::new(),
+            property_type: None,
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Byte offset in the file at which the error occurred, if it is known
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Path of the property the error occurred in, e.g. `CharacterSlots[3].DNA.HairColor`
+    ///
+    /// It is empty if the error didn't occur within a property.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Type of the innermost property of the path, e.g. `FloatProperty`
+    pub fn property_type(&self) -> Option<&str> {
+        self.property_type.as_deref()
+    }
+
+    /// Sets the offset unless it is already known
+    pub(crate) fn at_offset(mut self, offset: usize) -> Error {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Prepends the name of the property containing the path
+    pub(crate) fn in_property(mut self, name: &str) -> Error {
+        self.path = match self.path.chars().next() {
+            None => name.to_string(),
+            Some('[') => format!("{}{}", name, self.path),
+            Some(_) => format!("{}.{}", name, self.path),
+        };
+        self
+    }
+
+    /// Sets the type of the property unless the error occurred in a property nested in it
+    pub(crate) fn of_type(mut self, typ: &str) -> Error {
+        self.property_type.get_or_insert_with(|| typ.to_string());
+        self
+    }
+
+    /// Prepends the index of the element of an array, set or map containing the path
+    pub(crate) fn in_element(mut self, index: usize) -> Error {
+        self.path = match self.path.chars().next() {
+            None | Some('[') => format!("[{}]{}", index, self.path),
+            Some(_) => format!("[{}].{}", index, self.path),
+        };
+        self
+    }
+}
+
+/// Errors created without offset, which is filled in by the Deserializer as they propagate
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            offset: None,
+            path: String::new(),
+            property_type: None,
         }
     }
 }
 
 impl ser::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::new(ErrorKind::Message(msg.to_string()), 0)
+        Error::from(ErrorKind::Message(msg.to_string()))
     }
 }
 
 impl de::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::new(ErrorKind::Message(msg.to_string()), 0)
+        Error::from(ErrorKind::Message(msg.to_string()))
     }
 }
 
@@ -67,15 +169,46 @@ impl Display for Error {
         match &self.kind {
             ErrorKind::Message(msg) => write!(fmt, "{}", msg)?,
             ErrorKind::Io(e) => e.fmt(fmt)?,
+            ErrorKind::UnexpectedEof => write!(fmt, "unexpected end of file")?,
+            ErrorKind::InvalidMagic(magic) => write!(fmt, "invalid magic {:?}, expected `GVAS`", magic)?,
+            ErrorKind::UnsupportedCustomVersionFormat(format) => write!(fmt, "unsupported custom version format {}", format)?,
+            ErrorKind::UnsupportedType(typ) => write!(fmt, "unsupported type: {}", typ)?,
             ErrorKind::StringNotZeroTerminated(s) => write!(fmt, "string `{:?}` is not zero terminated", s)?,
+            ErrorKind::InvalidUtf16(s) => write!(fmt, "string `{:?}` is invalid UTF-16", s)?,
+            ErrorKind::InvalidBool(b) => write!(fmt, "invalid bool {}", b)?,
             ErrorKind::InvalidStringLength(len) => write!(fmt, "invalid string length {}", len)?,
+            ErrorKind::InvalidBoolLength(len) => write!(fmt, "invalid bool length {}", len)?,
+            ErrorKind::InvalidByteLength(len) => write!(fmt, "invalid byte length {}", len)?,
+            ErrorKind::InvalidInt8Length(len) => write!(fmt, "invalid int8 length {}", len)?,
+            ErrorKind::InvalidInt16Length(len) => write!(fmt, "invalid int16 length {}", len)?,
             ErrorKind::InvalidIntLength(len) => write!(fmt, "invalid int length {}", len)?,
+            ErrorKind::InvalidInt64Length(len) => write!(fmt, "invalid int64 length {}", len)?,
+            ErrorKind::InvalidUInt16Length(len) => write!(fmt, "invalid uint16 length {}", len)?,
+            ErrorKind::InvalidUInt32Length(len) => write!(fmt, "invalid uint32 length {}", len)?,
+            ErrorKind::InvalidUInt64Length(len) => write!(fmt, "invalid uint64 length {}", len)?,
             ErrorKind::InvalidQwordLength(len) => write!(fmt, "invalid qword length {}", len)?,
             ErrorKind::InvalidFloatLength(len) => write!(fmt, "invalid float length {}", len)?,
+            ErrorKind::InvalidDoubleLength(len) => write!(fmt, "invalid double length {}", len)?,
+            ErrorKind::UnknownPropertyType(typ) => write!(fmt, "unknown property type `{}`", typ)?,
+            ErrorKind::SizeMismatch { expected, actual } => write!(fmt, "size {} differs from the declared size {}", actual, expected)?,
+            ErrorKind::InvalidGuid(s) => write!(fmt, "invalid GUID `{}`", s)?,
+            ErrorKind::InvalidDateTime(s) => write!(fmt, "invalid date and time `{}`", s)?,
+            ErrorKind::InvalidTimespan(s) => write!(fmt, "invalid timespan `{}`", s)?,
+            ErrorKind::InvalidTextHistory(typ) => write!(fmt, "invalid text history type {}", typ)?,
+            ErrorKind::InvalidFormatArgumentType(typ) => write!(fmt, "invalid format argument type {}", typ)?,
+            ErrorKind::StringTooLong(len) => write!(fmt, "string of {} bytes exceeds the maximum string length", len)?,
+            ErrorKind::TooManyElements(count) => write!(fmt, "count {} exceeds the maximum element count", count)?,
+            ErrorKind::NestingTooDeep(depth) => write!(fmt, "nesting exceeds the maximum depth {}", depth)?,
+            ErrorKind::TooManyBytes(limit) => write!(fmt, "file exceeds the maximum size of {} bytes", limit)?,
+        }
+        if !self.path.is_empty() {
+            write!(fmt, " in `{}`", self.path)?;
         }
-        match self.kind {
-            ErrorKind::Message(_) | ErrorKind::Io(_) => {},
-            _ => write!(fmt, " at offset {}", self.offset)?,
+        if let Some(typ) = &self.property_type {
+            write!(fmt, " ({})", typ)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {}", offset)?;
         }
         Ok(())
     }
@@ -85,6 +218,12 @@ impl std::error::Error for Error {}
 
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
-        Error::new(ErrorKind::Io(e), 0)
+        if let Some(&TotalBytesExceeded(limit)) = e.get_ref().and_then(|e| e.downcast_ref()) {
+            return Error::from(ErrorKind::TooManyBytes(limit));
+        }
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            return Error::from(ErrorKind::UnexpectedEof);
+        }
+        Error::from(ErrorKind::Io(e))
     }
 }
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct `FieldPath` is (de)serialized as
+pub(crate) const FIELD_PATH_TOKEN: &str = "$serde_gvas::FieldPath";
+
+/// Path of a property as stored in a FieldPathProperty, i.e. Unreal's `FFieldPath`
+///
+/// A FieldPathProperty can be deserialized into a `String`, which results in the string
+/// returned by `Display`, e.g. `/Script/Engine.Actor:bHidden`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    /// Names from the field to its outermost owner, i.e. in reverse order
+    pub path: Vec<String>,
+    /// Path of the struct owning the field
+    pub owner: String,
+}
+
+/// Formats the path like Unreal does, starting with the outermost name
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, name) in self.path.iter().rev().enumerate() {
+            match i {
+                0 => {},
+                1 => f.write_str(":")?,
+                _ => f.write_str(".")?,
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fields of `FieldPath`, which is a newtype struct around them
+#[derive(Serialize, Deserialize)]
+struct FieldPathFields<P, S> {
+    path: P,
+    owner: S,
+}
+
+impl Serialize for FieldPath {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(FIELD_PATH_TOKEN, &FieldPathFields { path: &self.path, owner: &self.owner })
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldPath {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<FieldPath, D::Error> {
+        d.deserialize_newtype_struct(FIELD_PATH_TOKEN, FieldPathVisitor)
+    }
+}
+
+struct FieldPathVisitor;
+
+impl<'de> Visitor<'de> for FieldPathVisitor {
+    type Value = FieldPath;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a FieldPathProperty")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<FieldPath, D::Error> {
+        let fields: FieldPathFields<Vec<String>, String> = FieldPathFields::deserialize(d)?;
+        Ok(FieldPath { path: fields.path, owner: fields.owner })
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct `GameplayTag` is serialized as
+pub(crate) const GAMEPLAY_TAG_TOKEN: &str = "$serde_gvas::GameplayTag";
+/// Name of the newtype struct `GameplayTagContainer` is serialized as
+pub(crate) const GAMEPLAY_TAG_CONTAINER_TOKEN: &str = "$serde_gvas::GameplayTagContainer";
+
+/// Hierarchical name like `Ability.Attack.Melee` as stored in a StructProperty of type
+/// `GameplayTag`, i.e. Unreal's `FGameplayTag`
+///
+/// It is (de)serialized as string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameplayTag(pub String);
+
+/// Tags as stored in a StructProperty of type `GameplayTagContainer`, i.e. Unreal's
+/// `FGameplayTagContainer`
+///
+/// It is (de)serialized as sequence of strings, so such a property can be deserialized into a
+/// `Vec<String>` or `HashSet<String>` as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GameplayTagContainer(pub Vec<GameplayTag>);
+
+impl fmt::Display for GameplayTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for GameplayTag {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(GAMEPLAY_TAG_TOKEN, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameplayTag {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<GameplayTag, D::Error> {
+        d.deserialize_newtype_struct(GAMEPLAY_TAG_TOKEN, GameplayTagVisitor)
+    }
+}
+
+struct GameplayTagVisitor;
+
+impl<'de> Visitor<'de> for GameplayTagVisitor {
+    type Value = GameplayTag;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a gameplay tag string or GameplayTag struct")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<GameplayTag, D::Error> {
+        d.deserialize_str(self)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<GameplayTag, E> {
+        Ok(GameplayTag(s.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<GameplayTag, E> {
+        Ok(GameplayTag(s))
+    }
+
+    /// Visits the properties of a StructProperty
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<GameplayTag, A::Error> {
+        let mut tag_name = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "TagName" => tag_name = Some(map.next_value()?),
+                _ => { map.next_value::<de::IgnoredAny>()?; },
+            }
+        }
+        tag_name.map(GameplayTag).ok_or_else(|| de::Error::missing_field("TagName"))
+    }
+}
+
+impl Serialize for GameplayTagContainer {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(GAMEPLAY_TAG_CONTAINER_TOKEN, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameplayTagContainer {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<GameplayTagContainer, D::Error> {
+        d.deserialize_newtype_struct(GAMEPLAY_TAG_CONTAINER_TOKEN, GameplayTagContainerVisitor)
+    }
+}
+
+struct GameplayTagContainerVisitor;
+
+impl<'de> Visitor<'de> for GameplayTagContainerVisitor {
+    type Value = GameplayTagContainer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of gameplay tags")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<GameplayTagContainer, D::Error> {
+        d.deserialize_seq(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<GameplayTagContainer, A::Error> {
+        let mut tags = Vec::new();
+        while let Some(tag) = seq.next_element()? {
+            tags.push(tag);
+        }
+        Ok(GameplayTagContainer(tags))
+    }
+}
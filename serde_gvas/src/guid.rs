@@ -0,0 +1,132 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use error::{Error, ErrorKind};
+
+/// Name of the newtype struct `Guid` is serialized as
+///
+/// It allows the Serializer to write a `Guid` as StructProperty, while other formats see a string.
+pub(crate) const GUID_TOKEN: &str = "$serde_gvas::Guid";
+
+/// GUID as stored in GVAS files, which is four little endian u32
+///
+/// It is formatted like Unreal formats GUIDs with hyphens,
+/// e.g. `01234567-89AB-CDEF-0123-456789ABCDEF`, and (de)serialized as such a string.
+/// A field of this type is written as StructProperty of type `Guid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Guid(pub [u8; 16]);
+
+impl Guid {
+    /// Returns whether all bytes are zero, which is used for "no GUID"
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0u8; 16]
+    }
+
+    /// Creates a GUID from the four u32 Unreal's `FGuid` consists of
+    fn from_components(components: [u32; 4]) -> Guid {
+        let mut bytes = [0u8; 16];
+        for (i, component) in components.iter().enumerate() {
+            for j in 0..4 {
+                bytes[i * 4 + j] = (component >> (j * 8)) as u8;
+            }
+        }
+        Guid(bytes)
+    }
+
+    /// The four u32 Unreal's `FGuid` consists of
+    fn components(&self) -> [u32; 4] {
+        let mut res = [0u32; 4];
+        for (i, chunk) in self.0.chunks(4).enumerate() {
+            res[i] = u32::from(chunk[0]) | u32::from(chunk[1]) << 8 | u32::from(chunk[2]) << 16 | u32::from(chunk[3]) << 24;
+        }
+        res
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d] = self.components();
+        write!(f, "{:08X}-{:04X}-{:04X}-{:04X}-{:04X}{:08X}", a, b >> 16, b & 0xFFFF, c >> 16, c & 0xFFFF, d)
+    }
+}
+
+/// Parses 32 hex digits, optionally separated by hyphens
+impl FromStr for Guid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Guid, Error> {
+        let digits: Vec<u32> = s.chars()
+            .filter(|&c| c != '-')
+            .map(|c| c.to_digit(16))
+            .collect::<Option<_>>()
+            .filter(|digits: &Vec<u32>| digits.len() == 32)
+            .ok_or_else(|| Error::from(ErrorKind::InvalidGuid(s.to_string())))?;
+        let mut bytes = [0u8; 16];
+        for (i, component) in digits.chunks(8).enumerate() {
+            let component = component.iter().fold(0, |acc, &digit| acc << 4 | digit);
+            bytes[i * 4..i * 4 + 4].copy_from_slice(&[
+                component as u8,
+                (component >> 8) as u8,
+                (component >> 16) as u8,
+                (component >> 24) as u8,
+            ]);
+        }
+        Ok(Guid(bytes))
+    }
+}
+
+impl Serialize for Guid {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(GUID_TOKEN, &self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Guid {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Guid, D::Error> {
+        d.deserialize_newtype_struct(GUID_TOKEN, GuidVisitor)
+    }
+}
+
+struct GuidVisitor;
+
+impl<'de> Visitor<'de> for GuidVisitor {
+    type Value = Guid;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GUID string or Guid struct")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<Guid, D::Error> {
+        d.deserialize_str(self)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Guid, E> {
+        s.parse().map_err(E::custom)
+    }
+
+    /// Visits the fields of a StructProperty of type `Guid`
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Guid, A::Error> {
+        let mut components = [None; 4];
+        while let Some(key) = map.next_key::<String>()? {
+            let i = match key.as_str() {
+                "A" => 0,
+                "B" => 1,
+                "C" => 2,
+                "D" => 3,
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                    continue;
+                }
+            };
+            components[i] = Some(map.next_value()?);
+        }
+        let mut res = [0u32; 4];
+        for ((res, component), name) in res.iter_mut().zip(&components).zip(&["A", "B", "C", "D"]) {
+            *res = component.ok_or_else(|| de::Error::missing_field(name))?;
+        }
+        Ok(Guid::from_components(res))
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{Read, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+
+use de::{parse_string, CountingReader, DeserializerOptions};
+use error::{Result, Error, ErrorKind};
+use ser::write_string;
+
+/// Save game version from which the header contains custom versions
+const SAVE_GAME_VERSION_ADDED_CUSTOM_VERSIONS: u32 = 2;
+/// Save game version from which the header contains the UE5 package version
+const SAVE_GAME_VERSION_PACKAGE_FILE_SUMMARY_VERSION_CHANGE: u32 = 3;
+
+/// The only custom version format used by save games, where each version is a GUID and a number
+const CUSTOM_VERSION_FORMAT_OPTIMIZED: u32 = 3;
+
+// Header: ("GVAS", SaveGameVersionU32, PackageVersionU32, [PackageVersionUE5U32], EngineVersion, [CustomVersions], SaveGameClassString)
+// EngineVersion: (MajorU16, MinorU16, PatchU16, ChangelistU32, BranchString)
+// CustomVersions: (FormatU32, countU32, [(Guid, VersionU32)])
+
+/// Header of a GVAS file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GvasHeader {
+    /// Version of the save game format itself, which determines which fields are present
+    pub save_game_version: u32,
+    /// UE4 package file version
+    pub package_version: u32,
+    /// UE5 package file version, only present from save game version 3 onwards
+    pub package_version_ue5: u32,
+    pub engine_version: EngineVersion,
+    /// Format of the custom versions, only present from save game version 2 onwards
+    pub custom_version_format: u32,
+    /// Custom versions, only present from save game version 2 onwards
+    pub custom_versions: Vec<CustomVersion>,
+    /// Name of the class of the serialized save game
+    pub save_game_class_name: String,
+    /// Bytes following the properties of the save game object, which are kept to write
+    /// them back unchanged
+    ///
+    /// They aren't part of the header, but belong to the file as a whole like the header.
+    /// They are filled by `from_reader_with_header`, not by `GvasHeader::read`.
+    /// If empty, `to_writer` writes the 4 zero bytes Unreal writes.
+    pub trailing_bytes: Vec<u8>,
+}
+
+/// Version of the engine the file was saved with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub changelist: u32,
+    pub branch: String,
+}
+
+/// UE4 and UE5 package file versions, which determine the layout of properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PackageVersion {
+    pub(crate) ue4: u32,
+    /// Zero for files saved with UE4
+    pub(crate) ue5: u32,
+}
+
+/// Version of a subsystem, identified by its GUID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomVersion {
+    pub key: [u8; 16],
+    pub version: u32,
+}
+
+impl GvasHeader {
+    /// Package versions the properties following the header were saved with
+    pub(crate) fn package_versions(&self) -> PackageVersion {
+        PackageVersion { ue4: self.package_version, ue5: self.package_version_ue5 }
+    }
+
+    /// Reads the header of a GVAS file including the save game class name
+    ///
+    /// Afterwards the reader is positioned at the first property.
+    pub fn read<R: Read>(r: R) -> Result<GvasHeader> {
+        trace!("GvasHeader::read");
+        // the header is at the start of the file
+        let mut r = CountingReader::new(r, 0);
+        GvasHeader::parse(&mut r, DeserializerOptions::default().max_string_length).map_err(|e| e.at_offset(r.position()))
+    }
+
+    /// Parses the header, whose strings mustn't be longer than the given length in bytes
+    pub(crate) fn parse<R: Read>(r: &mut CountingReader<R>, max_string_length: usize) -> Result<GvasHeader> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != b"GVAS" {
+            return Err(Error::new(ErrorKind::InvalidMagic(magic), 0));
+        }
+        let save_game_version = r.read_u32::<LE>()?;
+        let package_version = r.read_u32::<LE>()?;
+        let mut package_version_ue5 = 0;
+        if save_game_version >= SAVE_GAME_VERSION_PACKAGE_FILE_SUMMARY_VERSION_CHANGE {
+            package_version_ue5 = r.read_u32::<LE>()?;
+        }
+        let major = r.read_u16::<LE>()?;
+        let minor = r.read_u16::<LE>()?;
+        let patch = r.read_u16::<LE>()?;
+        let changelist = r.read_u32::<LE>()?;
+        let branch = parse_string(r, max_string_length)?;
+        let engine_version = EngineVersion { major, minor, patch, changelist, branch };
+        let mut custom_version_format = 0;
+        let mut custom_versions = Vec::new();
+        if save_game_version >= SAVE_GAME_VERSION_ADDED_CUSTOM_VERSIONS {
+            let offset = r.position();
+            custom_version_format = r.read_u32::<LE>()?;
+            if custom_version_format != CUSTOM_VERSION_FORMAT_OPTIMIZED {
+                return Err(Error::new(ErrorKind::UnsupportedCustomVersionFormat(custom_version_format), offset));
+            }
+            let count = r.read_u32::<LE>()?;
+            for _ in 0..count {
+                let mut key = [0u8; 16];
+                r.read_exact(&mut key)?;
+                let version = r.read_u32::<LE>()?;
+                custom_versions.push(CustomVersion { key, version });
+            }
+        }
+        let save_game_class_name = parse_string(r, max_string_length)?;
+        let header = GvasHeader {
+            save_game_version,
+            package_version,
+            package_version_ue5,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            save_game_class_name,
+            trailing_bytes: Vec::new(),
+        };
+        trace!("{:?}", header);
+        Ok(header)
+    }
+
+    /// Writes the header including the save game class name
+    pub fn write<W: Write>(&self, mut w: W) -> Result<()> {
+        trace!("GvasHeader::write");
+        w.write_all(b"GVAS")?;
+        w.write_u32::<LE>(self.save_game_version)?;
+        w.write_u32::<LE>(self.package_version)?;
+        if self.save_game_version >= SAVE_GAME_VERSION_PACKAGE_FILE_SUMMARY_VERSION_CHANGE {
+            w.write_u32::<LE>(self.package_version_ue5)?;
+        }
+        w.write_u16::<LE>(self.engine_version.major)?;
+        w.write_u16::<LE>(self.engine_version.minor)?;
+        w.write_u16::<LE>(self.engine_version.patch)?;
+        w.write_u32::<LE>(self.engine_version.changelist)?;
+        write_string(&mut w, &self.engine_version.branch)?;
+        if self.save_game_version >= SAVE_GAME_VERSION_ADDED_CUSTOM_VERSIONS {
+            w.write_u32::<LE>(self.custom_version_format)?;
+            w.write_u32::<LE>(self.custom_versions.len() as u32)?;
+            for custom_version in &self.custom_versions {
+                w.write_all(&custom_version.key)?;
+                w.write_u32::<LE>(custom_version.version)?;
+            }
+        }
+        write_string(&mut w, &self.save_game_class_name)?;
+        Ok(())
+    }
+}
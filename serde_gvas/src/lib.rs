@@ -1,7 +1,8 @@
-#[macro_use]
 extern crate failure;
 #[macro_use]
 extern crate serde;
+#[macro_use]
+extern crate serde_derive;
 extern crate byteorder;
 #[macro_use]
 extern crate log;
@@ -9,12 +10,34 @@ extern crate encoding_rs;
 extern crate void;
 
 mod error;
+mod guid;
+mod header;
 mod de;
 mod ser;
+mod value;
+mod text;
+mod soft_object;
+mod delegate;
+mod field_path;
+mod date_time;
+mod gameplay_tag;
+mod native;
+mod struct_codec;
+mod content;
+pub mod set;
+pub mod typed;
 
-pub use error::{Error, Result};
-pub use de::{Deserializer, MapDeserializer};
-pub use ser::Serializer;
-
-// TODO: from_bytes, from_reader
-// TODO: to_XXX
\ No newline at end of file
+pub use error::{Error, ErrorKind, Result};
+pub use guid::Guid;
+pub use header::{GvasHeader, EngineVersion, CustomVersion};
+pub use de::{Deserializer, DeserializerOptions, MapDeserializer, from_reader, from_reader_with_header, from_reader_with_codecs, from_reader_with_options, from_slice, from_path};
+pub use ser::{Serializer, PropertiesSerializer, to_writer, to_writer_with_codecs, to_vec};
+pub use value::{Value, Property, StructType, from_value};
+pub use text::{FText, TextHistory, FormatArgumentValue, NumberFormattingOptions};
+pub use soft_object::SoftObjectPath;
+pub use delegate::{Delegate, MulticastDelegate};
+pub use field_path::FieldPath;
+pub use date_time::{DateTime, Timespan};
+pub use gameplay_tag::{GameplayTag, GameplayTagContainer};
+pub use native::{Vector, Vector2D, Vector4, Rotator, Quat, BoundingBox, LinearColor, Color, IntPoint, IntVector, Transform};
+pub use struct_codec::{StructCodecRegistry, StructDecoder, StructEncoder};
@@ -0,0 +1,246 @@
+//! Structs whose StructProperty body is raw binary instead of a list of properties
+//!
+//! Their fields are (de)serialized like the properties of other structs, so the types in
+//! this module are plain structs with the field names Unreal uses.
+
+/// Type of a field of a native struct as stored in the struct's body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NativeField {
+    /// Floating point number, which is a float before UE5 large world coordinates and
+    /// a double afterwards
+    Real,
+    Float,
+    /// Bool stored as u8
+    Bool,
+    /// Raw u8
+    Byte,
+    Int,
+    Int64,
+    UInt32,
+    /// Count followed by names, which is represented like an ArrayProperty of names
+    NameArray,
+    /// Nested native struct with its struct type name and fields
+    Struct(&'static str, &'static [(&'static str, NativeField)]),
+}
+
+impl NativeField {
+    /// Description used in errors about fields of the wrong type
+    pub(crate) fn description(&self) -> &'static str {
+        match self {
+            NativeField::Real | NativeField::Float => "a float or double",
+            NativeField::Bool => "a bool",
+            NativeField::Byte => "a u8",
+            NativeField::Int => "an i32",
+            NativeField::Int64 => "an i64",
+            NativeField::UInt32 => "a u32",
+            NativeField::NameArray => "a sequence of strings",
+            NativeField::Struct(name, _) => name,
+        }
+    }
+}
+
+const VECTOR: &[(&str, NativeField)] = &[("X", NativeField::Real), ("Y", NativeField::Real), ("Z", NativeField::Real)];
+const VECTOR2D: &[(&str, NativeField)] = &[("X", NativeField::Real), ("Y", NativeField::Real)];
+const VECTOR4: &[(&str, NativeField)] = &[
+    ("X", NativeField::Real),
+    ("Y", NativeField::Real),
+    ("Z", NativeField::Real),
+    ("W", NativeField::Real),
+];
+const ROTATOR: &[(&str, NativeField)] = &[("Pitch", NativeField::Real), ("Yaw", NativeField::Real), ("Roll", NativeField::Real)];
+const QUAT: &[(&str, NativeField)] = &[
+    ("X", NativeField::Real),
+    ("Y", NativeField::Real),
+    ("Z", NativeField::Real),
+    ("W", NativeField::Real),
+];
+const BOX: &[(&str, NativeField)] = &[
+    ("Min", NativeField::Struct("Vector", VECTOR)),
+    ("Max", NativeField::Struct("Vector", VECTOR)),
+    ("IsValid", NativeField::Bool),
+];
+const LINEAR_COLOR: &[(&str, NativeField)] = &[
+    ("R", NativeField::Float),
+    ("G", NativeField::Float),
+    ("B", NativeField::Float),
+    ("A", NativeField::Float),
+];
+const COLOR: &[(&str, NativeField)] = &[
+    ("B", NativeField::Byte),
+    ("G", NativeField::Byte),
+    ("R", NativeField::Byte),
+    ("A", NativeField::Byte),
+];
+const INT_POINT: &[(&str, NativeField)] = &[("X", NativeField::Int), ("Y", NativeField::Int)];
+const INT_VECTOR: &[(&str, NativeField)] = &[("X", NativeField::Int), ("Y", NativeField::Int), ("Z", NativeField::Int)];
+const GUID: &[(&str, NativeField)] = &[
+    ("A", NativeField::UInt32),
+    ("B", NativeField::UInt32),
+    ("C", NativeField::UInt32),
+    ("D", NativeField::UInt32),
+];
+const TICKS: &[(&str, NativeField)] = &[("Ticks", NativeField::Int64)];
+const GAMEPLAY_TAG_CONTAINER: &[(&str, NativeField)] = &[("GameplayTags", NativeField::NameArray)];
+
+/// Returns the fields of a native struct in the order they are serialized in
+///
+/// Returns `None` if the struct isn't a native struct.
+pub(crate) fn native_struct_fields(name: &str) -> Option<&'static [(&'static str, NativeField)]> {
+    match name {
+        "Vector" => Some(VECTOR),
+        "Vector2D" => Some(VECTOR2D),
+        "Vector4" => Some(VECTOR4),
+        "Rotator" => Some(ROTATOR),
+        "Quat" => Some(QUAT),
+        "Box" => Some(BOX),
+        "LinearColor" => Some(LINEAR_COLOR),
+        "Color" => Some(COLOR),
+        "IntPoint" => Some(INT_POINT),
+        "IntVector" => Some(INT_VECTOR),
+        "Guid" => Some(GUID),
+        "DateTime" | "Timespan" => Some(TICKS),
+        "GameplayTagContainer" => Some(GAMEPLAY_TAG_CONTAINER),
+        _ => None,
+    }
+}
+
+/// `FVector`, which is stored as three floats or, from UE5 onwards, three doubles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector {
+    #[serde(rename = "X")]
+    pub x: f64,
+    #[serde(rename = "Y")]
+    pub y: f64,
+    #[serde(rename = "Z")]
+    pub z: f64,
+}
+
+/// `FVector2D`, which is stored as two floats or, from UE5 onwards, two doubles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector2D {
+    #[serde(rename = "X")]
+    pub x: f64,
+    #[serde(rename = "Y")]
+    pub y: f64,
+}
+
+/// `FVector4`, which is stored as four floats or, from UE5 onwards, four doubles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector4 {
+    #[serde(rename = "X")]
+    pub x: f64,
+    #[serde(rename = "Y")]
+    pub y: f64,
+    #[serde(rename = "Z")]
+    pub z: f64,
+    #[serde(rename = "W")]
+    pub w: f64,
+}
+
+/// `FRotator` in degrees
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rotator {
+    #[serde(rename = "Pitch")]
+    pub pitch: f64,
+    #[serde(rename = "Yaw")]
+    pub yaw: f64,
+    #[serde(rename = "Roll")]
+    pub roll: f64,
+}
+
+/// `FQuat`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Quat {
+    #[serde(rename = "X")]
+    pub x: f64,
+    #[serde(rename = "Y")]
+    pub y: f64,
+    #[serde(rename = "Z")]
+    pub z: f64,
+    #[serde(rename = "W")]
+    pub w: f64,
+}
+
+/// `FBox`, i.e. an axis aligned bounding box
+///
+/// It is named `Box` in Unreal, which would shadow `std::boxed::Box`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "Box")]
+pub struct BoundingBox {
+    #[serde(rename = "Min")]
+    pub min: Vector,
+    #[serde(rename = "Max")]
+    pub max: Vector,
+    #[serde(rename = "IsValid")]
+    pub is_valid: bool,
+}
+
+/// `FLinearColor`, whose components are floats in all engine versions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinearColor {
+    #[serde(rename = "R")]
+    pub r: f32,
+    #[serde(rename = "G")]
+    pub g: f32,
+    #[serde(rename = "B")]
+    pub b: f32,
+    #[serde(rename = "A")]
+    pub a: f32,
+}
+
+/// `FColor`, which is stored in BGRA order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Color {
+    #[serde(rename = "B")]
+    pub b: u8,
+    #[serde(rename = "G")]
+    pub g: u8,
+    #[serde(rename = "R")]
+    pub r: u8,
+    #[serde(rename = "A")]
+    pub a: u8,
+}
+
+/// `FIntPoint`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IntPoint {
+    #[serde(rename = "X")]
+    pub x: i32,
+    #[serde(rename = "Y")]
+    pub y: i32,
+}
+
+/// `FIntVector`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IntVector {
+    #[serde(rename = "X")]
+    pub x: i32,
+    #[serde(rename = "Y")]
+    pub y: i32,
+    #[serde(rename = "Z")]
+    pub z: i32,
+}
+
+/// `FTransform`
+///
+/// In contrast to its fields, it isn't a native struct but stored as list of properties.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    #[serde(rename = "Rotation")]
+    pub rotation: Quat,
+    #[serde(rename = "Translation")]
+    pub translation: Vector,
+    #[serde(rename = "Scale3D")]
+    pub scale: Vector,
+}
+
+/// The identity transform, which Unreal uses as default as well
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform {
+            rotation: Quat { x: 0.0, y: 0.0, z: 0.0, w: 1.0 },
+            translation: Vector::default(),
+            scale: Vector { x: 1.0, y: 1.0, z: 1.0 },
+        }
+    }
+}
@@ -1,3 +1,1804 @@
-pub struct Serializer {
+use std::convert::TryFrom;
+use std::io::Write;
+use std::mem;
+use std::str::FromStr;
 
-}
\ No newline at end of file
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use serde::Deserialize;
+use serde::ser::{self, Serialize, Impossible};
+use void::ResultVoidExt;
+
+use content::ContentSerializer;
+use date_time::{DATE_TIME_TOKEN, TIMESPAN_TOKEN};
+use de::{PropertyTag, PropertyType, parse_value_from, VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG, VER_UE4_INNER_ARRAY_TAG_INFO,
+    VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG, VER_UE5_LARGE_WORLD_COORDINATES, VER_UE5_FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES};
+use delegate::{Delegate, MulticastDelegate, DELEGATE_TOKEN, MULTICAST_DELEGATE_TOKEN};
+use error::{Result, Error, ErrorKind};
+use field_path::{FieldPath, FIELD_PATH_TOKEN};
+use gameplay_tag::{GAMEPLAY_TAG_TOKEN, GAMEPLAY_TAG_CONTAINER_TOKEN};
+use header::{GvasHeader, PackageVersion};
+use native::{NativeField, native_struct_fields};
+use guid::{Guid, GUID_TOKEN};
+use set::SET_TOKEN;
+use soft_object::{SoftObjectPath, SOFT_OBJECT_PATH_TOKEN};
+use text::{FText, TextHistory, FormatArgumentValue, NumberFormattingOptions, TEXT_TOKEN};
+use typed::TYPED_TOKEN;
+use struct_codec::{StructCodec, StructCodecRegistry};
+use value::{Property, StructType, Value, VALUE_TOKEN};
+
+/// Element type of empty arrays, sets and maps whose element types are unknown
+///
+/// Serde only reveals the element type by serializing an element, so empty containers are written
+/// as containers of IntProperty unless they are serialized with `serde_gvas::typed`. This crate
+/// deserializes them into empty containers of any type, Unreal may refuse to load a container
+/// whose element type doesn't match and keep the default value of the property instead.
+const DEFAULT_ELEMENT_TYPE: PropertyType = PropertyType::Int;
+
+/// Serialize the given value as whole GVAS file with the given header
+///
+/// The value must be a struct or a map with string keys.
+pub fn to_writer<W: Write, T: Serialize + ?Sized>(w: W, header: &GvasHeader, value: &T) -> Result<()> {
+    to_writer_with_codecs(w, header, &StructCodecRegistry::default(), value)
+}
+
+/// Serialize the given value as whole GVAS file like `to_writer`, encoding structs with the given codecs
+pub fn to_writer_with_codecs<W: Write, T: Serialize + ?Sized>(mut w: W, header: &GvasHeader, codecs: &StructCodecRegistry, value: &T) -> Result<()> {
+    header.write(&mut w)?;
+    value.serialize(&mut Serializer::new(&mut w, header).with_struct_codecs(codecs.clone()))?;
+    if header.trailing_bytes.is_empty() {
+        // The save game object ends with a bool whether it has a GUID, which it doesn't
+        w.write_u32::<LE>(0)?;
+    } else {
+        w.write_all(&header.trailing_bytes)?;
+    }
+    Ok(())
+}
+
+/// Serialize the given value as whole GVAS file with the given header into a new Vec
+pub fn to_vec<T: Serialize + ?Sized>(header: &GvasHeader, value: &T) -> Result<Vec<u8>> {
+    let mut vec = Vec::new();
+    to_writer(&mut vec, header, value)?;
+    Ok(vec)
+}
+
+// Every property is first serialized into a buffer, as the tag in front of it contains its length.
+// The type of the property is only known after its value has been serialized. Therefore
+// serializing a value returns its tag, which is written in front of the buffer afterwards.
+
+/// Serializer writing the list of properties of a save game
+///
+/// It assumes that the header has already been written and only writes the properties,
+/// which are terminated by `None`.
+pub struct Serializer<W: Write> {
+    w: W,
+    package_version: PackageVersion,
+    struct_codecs: StructCodecRegistry,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Create a new Serializer for the properties following the given header
+    pub fn new(w: W, header: &GvasHeader) -> Serializer<W> {
+        Serializer {
+            w,
+            package_version: header.package_versions(),
+            struct_codecs: StructCodecRegistry::default(),
+        }
+    }
+
+    /// Encode structs with the given codecs
+    pub fn with_struct_codecs(mut self, codecs: StructCodecRegistry) -> Serializer<W> {
+        self.struct_codecs = codecs;
+        self
+    }
+}
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $ret:ty = $name:expr;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ret> {
+                Err(Error::from(ErrorKind::UnsupportedType($name)))
+            }
+        )*
+    }
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = PropertiesSerializer<'a, W>;
+    type SerializeStruct = PropertiesSerializer<'a, W>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(PropertiesSerializer::new(&mut self.w, self.package_version, &self.struct_codecs))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(PropertiesSerializer::new(&mut self.w, self.package_version, &self.struct_codecs))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> () = "bool as root";
+        serialize_i8(i8) -> () = "i8 as root";
+        serialize_i16(i16) -> () = "i16 as root";
+        serialize_i32(i32) -> () = "i32 as root";
+        serialize_i64(i64) -> () = "i64 as root";
+        serialize_u8(u8) -> () = "u8 as root";
+        serialize_u16(u16) -> () = "u16 as root";
+        serialize_u32(u32) -> () = "u32 as root";
+        serialize_u64(u64) -> () = "u64 as root";
+        serialize_f32(f32) -> () = "f32 as root";
+        serialize_f64(f64) -> () = "f64 as root";
+        serialize_char(char) -> () = "char as root";
+        serialize_str(&str) -> () = "str as root";
+        serialize_bytes(&[u8]) -> () = "bytes as root";
+        serialize_none() -> () = "none as root";
+        serialize_unit() -> () = "unit as root";
+        serialize_unit_struct(&'static str) -> () = "unit struct as root";
+        serialize_unit_variant(&'static str, u32, &'static str) -> () = "enum as root";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "sequence as root";
+        serialize_tuple(usize) -> Self::SerializeTuple = "tuple as root";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "tuple struct as root";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "enum as root";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "enum as root";
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<()> {
+        if name != VALUE_TOKEN || variant != "StructProperty" {
+            return Err(Error::from(ErrorKind::UnsupportedType("enum as root")));
+        }
+        // A `Value` is written like the body of a StructProperty, whose struct type name is
+        // the save game class name, which is part of the header instead.
+        let mut body = Vec::new();
+        let ser = ValueSerializer::new(&mut body, self.package_version, &self.struct_codecs, "", false);
+        value.serialize(PayloadSerializer::new(ser, PropertyType::Struct))?;
+        self.w.write_all(&body)?;
+        Ok(())
+    }
+}
+
+/// Writes each field of a struct or entry of a map as property and terminates them with `None`
+pub struct PropertiesSerializer<'a, W: Write + 'a> {
+    w: &'a mut W,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    key: Option<String>,
+}
+
+impl<'a, W: Write + 'a> PropertiesSerializer<'a, W> {
+    fn new(w: &'a mut W, package_version: PackageVersion, codecs: &'a StructCodecRegistry) -> PropertiesSerializer<'a, W> {
+        PropertiesSerializer { w, package_version, codecs, key: None }
+    }
+
+    fn write_property<T: Serialize + ?Sized>(&mut self, name: &str, array_index: u32, guid: Option<Guid>, value: &T) -> Result<()> {
+        trace!("write_property: {:?}", name);
+        let mut body = Vec::new();
+        let tag = value.serialize(ValueSerializer::new(&mut body, self.package_version, self.codecs, name, false))
+            .map_err(|e| e.in_property(name))?;
+        let mut tag = match tag {
+            Some(tag) => tag,
+            // missing properties are initialized with their default value when loaded
+            None => return Ok(()),
+        };
+        tag.len = Some(body.len() as u32);
+        tag.array_index = array_index;
+        tag.guid = guid;
+        write_string(&mut *self.w, name)?;
+        write_tag(&mut *self.w, self.package_version, &tag)?;
+        self.w.write_all(&body)?;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        write_string(self.w, "None")?;
+        Ok(())
+    }
+}
+
+impl<'a, W: Write + 'a> ser::SerializeStruct for PropertiesSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.write_property(key, 0, None, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a, W: Write + 'a> ser::SerializeMap for PropertiesSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(serialize_string(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("serialize_value called before serialize_key")),
+        };
+        self.write_property(&key, 0, None, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+/// Serializes the body of a property, returning its tag
+///
+/// Returns `None` if the property should be omitted, e.g. for `Option::None`.
+/// Array elements don't have a tag of their own, so bools are written into the body instead.
+struct ValueSerializer<'a> {
+    w: &'a mut Vec<u8>,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    /// Name of the property, needed for the tag of arrays of structs
+    name: &'a str,
+    in_array: bool,
+    /// Whether sequences are written as SetProperty instead of ArrayProperty
+    set: bool,
+    /// Tag of the elements of a sequence or the keys of a map if known beforehand
+    inner: Option<PropertyTag>,
+    /// Tag of the values of a map if known beforehand
+    value: Option<PropertyTag>,
+}
+
+impl<'a> ValueSerializer<'a> {
+    fn new(w: &'a mut Vec<u8>, package_version: PackageVersion, codecs: &'a StructCodecRegistry, name: &'a str, in_array: bool) -> ValueSerializer<'a> {
+        ValueSerializer { w, package_version, codecs, name, in_array, set: false, inner: None, value: None }
+    }
+}
+
+impl<'a> ser::Serializer for ValueSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+    type SerializeSeq = ArraySerializer<'a>;
+    type SerializeTuple = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleVariant = Impossible<Option<PropertyTag>, Error>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = Impossible<Option<PropertyTag>, Error>;
+
+    /// Types like `DateTime` pass their raw data instead of a string
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Option<PropertyTag>> {
+        let mut tag = PropertyTag::new(PropertyType::Bool, None);
+        if self.in_array {
+            self.w.write_u8(v as u8)?;
+        } else {
+            tag.bool_value = Some(v);
+        }
+        Ok(Some(tag))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Option<PropertyTag>> {
+        self.w.write_u8(v)?;
+        // a ByteProperty without enum holds a raw byte
+        let mut tag = PropertyTag::new(PropertyType::Byte, None);
+        tag.enum_name = Some("None".to_string());
+        Ok(Some(tag))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Option<PropertyTag>> {
+        self.w.write_i8(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Int8, None)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Option<PropertyTag>> {
+        self.w.write_i16::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Int16, None)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Option<PropertyTag>> {
+        self.w.write_i32::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Int, None)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Option<PropertyTag>> {
+        self.w.write_i64::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Int64, None)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Option<PropertyTag>> {
+        self.w.write_u16::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::UInt16, None)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Option<PropertyTag>> {
+        self.w.write_u32::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::UInt32, None)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Option<PropertyTag>> {
+        self.w.write_u64::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::UInt64, None)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Option<PropertyTag>> {
+        self.w.write_f32::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Float, None)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Option<PropertyTag>> {
+        self.w.write_f64::<LE>(v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Double, None)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Option<PropertyTag>> {
+        self.serialize_str(v.encode_utf8(&mut [0u8; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Option<PropertyTag>> {
+        write_string(&mut *self.w, v)?;
+        Ok(Some(PropertyTag::new(PropertyType::Str, None)))
+    }
+
+    /// Writes the bytes as an array of bytes, or as set if asked to
+    fn serialize_bytes(self, v: &[u8]) -> Result<Option<PropertyTag>> {
+        if self.in_array {
+            return Err(Error::from(ErrorKind::UnsupportedType("nested array")));
+        }
+        let typ = if self.set {
+            // no removed elements
+            self.w.write_u32::<LE>(0)?;
+            PropertyType::Set
+        } else {
+            PropertyType::Array
+        };
+        self.w.write_u32::<LE>(v.len() as u32)?;
+        self.w.write_all(v)?;
+        let mut tag = PropertyTag::new(typ, None);
+        tag.inner_type = Some(PropertyType::Byte);
+        Ok(Some(tag))
+    }
+
+    fn serialize_none(self) -> Result<Option<PropertyTag>> {
+        if self.in_array {
+            return Err(Error::from(ErrorKind::UnsupportedType("none in array")));
+        }
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Option<PropertyTag>> {
+        value.serialize(self)
+    }
+
+    /// Writes an EnumProperty, whose variants are prefixed with the enum name like Unreal does
+    ///
+    /// The enum name is the name of the Rust type, which can be changed with `#[serde(rename)]`.
+    fn serialize_unit_variant(self, name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Option<PropertyTag>> {
+        write_string(&mut *self.w, &format!("{}::{}", name, variant))?;
+        let mut tag = PropertyTag::new(PropertyType::Enum, None);
+        tag.enum_name = Some(name.to_string());
+        Ok(Some(tag))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<Option<PropertyTag>> {
+        // the types of this crate serialize their fields, which are read back to write them
+        let typ = match name {
+            SET_TOKEN => return value.serialize(ValueSerializer { set: true, ..self }),
+            TYPED_TOKEN => return value.serialize(TypedSerializer { ser: self, len: 0, position: 0, tag: None }),
+            TEXT_TOKEN => {
+                let text = FText::deserialize(value.serialize(ContentSerializer)?)?;
+                write_text(&mut *self.w, &text)?;
+                PropertyType::Text
+            }
+            SOFT_OBJECT_PATH_TOKEN => {
+                // SoftClassProperties can only be written from a `Value`, which overrides the type
+                let path = SoftObjectPath::deserialize(value.serialize(ContentSerializer)?)?;
+                write_soft_object_path(&mut *self.w, self.package_version, &path)?;
+                PropertyType::SoftObject
+            }
+            DELEGATE_TOKEN => {
+                let delegate = Delegate::deserialize(value.serialize(ContentSerializer)?)?;
+                write_delegate(&mut *self.w, &delegate)?;
+                PropertyType::Delegate
+            }
+            MULTICAST_DELEGATE_TOKEN => {
+                // the same goes for MulticastSparseDelegateProperties
+                let delegates = MulticastDelegate::deserialize(value.serialize(ContentSerializer)?)?;
+                self.w.write_u32::<LE>(delegates.0.len() as u32)?;
+                for delegate in &delegates.0 {
+                    write_delegate(&mut *self.w, delegate)?;
+                }
+                PropertyType::MulticastInlineDelegate
+            }
+            GUID_TOKEN => {
+                let guid: Guid = serialize_string(value)?.parse()?;
+                self.w.write_all(&guid.0)?;
+                return Ok(Some(struct_tag("Guid")));
+            }
+            GAMEPLAY_TAG_TOKEN => {
+                let tag_name = Value::Name(serialize_string(value)?);
+                PropertiesSerializer::new(&mut *self.w, self.package_version, self.codecs).write_property("TagName", 0, None, &tag_name)?;
+                write_string(&mut *self.w, "None")?;
+                return Ok(Some(struct_tag("GameplayTag")));
+            }
+            GAMEPLAY_TAG_CONTAINER_TOKEN => {
+                let tags = Vec::<String>::deserialize(value.serialize(ContentSerializer)?)?;
+                self.w.write_u32::<LE>(tags.len() as u32)?;
+                for tag in &tags {
+                    write_string(&mut *self.w, tag)?;
+                }
+                return Ok(Some(struct_tag("GameplayTagContainer")));
+            }
+            // this serializer isn't human-readable, so both are their ticks
+            DATE_TIME_TOKEN | TIMESPAN_TOKEN => {
+                let ticks = i64::deserialize(value.serialize(ContentSerializer)?)?;
+                self.w.write_i64::<LE>(ticks)?;
+                return Ok(Some(struct_tag(if name == DATE_TIME_TOKEN { "DateTime" } else { "Timespan" })));
+            }
+            FIELD_PATH_TOKEN => {
+                let path = FieldPath::deserialize(value.serialize(ContentSerializer)?)?;
+                self.w.write_u32::<LE>(path.path.len() as u32)?;
+                for name in &path.path {
+                    write_string(&mut *self.w, name)?;
+                }
+                write_string(&mut *self.w, &path.owner)?;
+                PropertyType::FieldPath
+            }
+            _ => return value.serialize(self),
+        };
+        Ok(Some(PropertyTag::new(typ, None)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ArraySerializer<'a>> {
+        if self.in_array {
+            return Err(Error::from(ErrorKind::UnsupportedType("nested array")));
+        }
+        Ok(ArraySerializer {
+            w: self.w,
+            package_version: self.package_version,
+            codecs: self.codecs,
+            name: self.name,
+            typ: if self.set { PropertyType::Set } else { PropertyType::Array },
+            removed: Vec::new(),
+            removed_count: 0,
+            elements: Vec::new(),
+            count: 0,
+            inner: self.inner,
+            inner_type: None,
+            struct_type: None,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer<'a>> {
+        if self.in_array {
+            return Err(Error::from(ErrorKind::UnsupportedType("map in array or map")));
+        }
+        let mut map = MapSerializer::new(self.w, self.package_version, self.codecs, self.name);
+        map.key = self.inner;
+        map.value = self.value;
+        Ok(map)
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<StructSerializer<'a>> {
+        Ok(StructSerializer::new(self.w, self.package_version, self.codecs, name.to_string(), Guid::default()))
+    }
+
+    unsupported! {
+        serialize_unit() -> Option<PropertyTag> = "unit";
+        serialize_unit_struct(&'static str) -> Option<PropertyTag> = "unit struct";
+        serialize_tuple(usize) -> Self::SerializeTuple = "tuple";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "tuple struct";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "enum with tuple variant";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "enum with struct variant";
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<Option<PropertyTag>> {
+        if name != VALUE_TOKEN {
+            return Err(Error::from(ErrorKind::UnsupportedType("enum with newtype variant")));
+        }
+        // a `Value`, whose variant is the type of the property
+        let typ = PropertyType::from_str(variant).void_unwrap();
+        match typ {
+            PropertyType::Byte | PropertyType::Enum | PropertyType::Array | PropertyType::Struct
+            | PropertyType::Map | PropertyType::Set | PropertyType::Unknown(_) =>
+                value.serialize(PayloadSerializer::new(self, typ)),
+            // the GUID is serialized as string, but written as 16 bytes
+            PropertyType::LazyObject => {
+                let guid = serialize_guid(value)?.unwrap_or_default();
+                self.w.write_all(&guid.0)?;
+                Ok(Some(PropertyTag::new(typ, None)))
+            }
+            _ => {
+                let tag = value.serialize(self)?;
+                Ok(tag.map(|mut tag| {
+                    tag.typ = typ;
+                    tag
+                }))
+            }
+        }
+    }
+}
+
+/// Serializes the elements of an array or set, which are written after their count
+///
+/// Sets are written like arrays, but preceded by their removed elements and without a tag
+/// for structs.
+struct ArraySerializer<'a> {
+    w: &'a mut Vec<u8>,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    name: &'a str,
+    /// Either ArrayProperty or SetProperty
+    typ: PropertyType,
+    /// Serialized removed elements of a set, which only `Value`s contain
+    removed: Vec<u8>,
+    removed_count: u32,
+    elements: Vec<u8>,
+    count: u32,
+    /// Tag of the first element, or the element type if known beforehand
+    inner: Option<PropertyTag>,
+    /// Element type if known beforehand, which allows writing empty arrays
+    inner_type: Option<PropertyType>,
+    /// Struct type of the elements if known beforehand
+    struct_type: Option<StructType>,
+}
+
+impl<'a> ser::SerializeSeq for ArraySerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let tag = value.serialize(ValueSerializer::new(&mut self.elements, self.package_version, self.codecs, self.name, true))?;
+        if self.inner.is_none() {
+            self.inner = tag;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        let mut inner = match (self.inner, self.inner_type) {
+            (Some(inner), _) => inner,
+            (None, Some(inner_type)) => PropertyTag::new(inner_type, None),
+            (None, None) => PropertyTag::new(DEFAULT_ELEMENT_TYPE, None),
+        };
+        if let Some(struct_type) = self.struct_type {
+            inner.struct_name = Some(struct_type.name);
+            inner.struct_guid = struct_type.guid;
+        }
+        if self.typ == PropertyType::Set {
+            self.w.write_u32::<LE>(self.removed_count)?;
+            self.w.write_all(&self.removed)?;
+        }
+        self.w.write_u32::<LE>(self.count)?;
+        if self.typ == PropertyType::Array && inner.typ == PropertyType::Struct
+            && self.package_version.ue4 >= VER_UE4_INNER_ARRAY_TAG_INFO {
+            let mut inner_tag = inner.clone();
+            inner_tag.len = Some(self.elements.len() as u32);
+            write_string(&mut *self.w, self.name)?;
+            write_tag(&mut *self.w, self.package_version, &inner_tag)?;
+        }
+        self.w.write_all(&self.elements)?;
+        let mut tag = PropertyTag::new(self.typ, None);
+        tag.inner_type = Some(inner.typ);
+        Ok(Some(tag))
+    }
+}
+
+/// Serializes the entries of a map, which are written after the removed keys and their count
+///
+/// Keys and values don't have tags of their own, so they are serialized like array elements.
+struct MapSerializer<'a> {
+    w: &'a mut Vec<u8>,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    name: &'a str,
+    /// Serialized removed keys, which only `Value`s contain
+    removed: Vec<u8>,
+    removed_count: u32,
+    entries: Vec<u8>,
+    count: u32,
+    /// Tag of the first key, or the key type if known beforehand
+    key: Option<PropertyTag>,
+    /// Tag of the first value, or the value type if known beforehand
+    value: Option<PropertyTag>,
+}
+
+impl<'a> MapSerializer<'a> {
+    fn new(w: &'a mut Vec<u8>, package_version: PackageVersion, codecs: &'a StructCodecRegistry, name: &'a str) -> MapSerializer<'a> {
+        MapSerializer {
+            w,
+            package_version,
+            codecs,
+            name,
+            removed: Vec::new(),
+            removed_count: 0,
+            entries: Vec::new(),
+            count: 0,
+            key: None,
+            value: None,
+        }
+    }
+
+    fn write_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let tag = key.serialize(ValueSerializer::new(&mut self.entries, self.package_version, self.codecs, self.name, true))?;
+        if self.key.is_none() {
+            self.key = tag;
+        }
+        Ok(())
+    }
+
+    fn write_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let tag = value.serialize(ValueSerializer::new(&mut self.entries, self.package_version, self.codecs, self.name, true))?;
+        if self.value.is_none() {
+            self.value = tag;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<PropertyTag>> {
+        let key = self.key.map_or(DEFAULT_ELEMENT_TYPE, |key| key.typ);
+        let value = self.value.map_or(DEFAULT_ELEMENT_TYPE, |value| value.typ);
+        self.w.write_u32::<LE>(self.removed_count)?;
+        self.w.write_all(&self.removed)?;
+        self.w.write_u32::<LE>(self.count)?;
+        self.w.write_all(&self.entries)?;
+        let mut tag = PropertyTag::new(PropertyType::Map, None);
+        tag.inner_type = Some(key);
+        tag.value_type = Some(value);
+        Ok(Some(tag))
+    }
+}
+
+impl<'a> ser::SerializeMap for MapSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.write_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.write_value(value)
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        self.finish()
+    }
+}
+
+/// Serializes the entries of a map contained in a `Value`, which are (key, value) tuples
+impl<'a> ser::SerializeSeq for MapSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(EntrySerializer { map: self, position: 0 })
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        self.finish()
+    }
+}
+
+/// Serializes an entry of a map contained in a `Value`, which is a (key, value) tuple
+struct EntrySerializer<'a, 'b: 'a> {
+    map: &'a mut MapSerializer<'b>,
+    position: usize,
+}
+
+impl<'a, 'b: 'a> ser::Serializer for EntrySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> () = "malformed Value";
+        serialize_i8(i8) -> () = "malformed Value";
+        serialize_i16(i16) -> () = "malformed Value";
+        serialize_i32(i32) -> () = "malformed Value";
+        serialize_i64(i64) -> () = "malformed Value";
+        serialize_u8(u8) -> () = "malformed Value";
+        serialize_u16(u16) -> () = "malformed Value";
+        serialize_u32(u32) -> () = "malformed Value";
+        serialize_u64(u64) -> () = "malformed Value";
+        serialize_f32(f32) -> () = "malformed Value";
+        serialize_f64(f64) -> () = "malformed Value";
+        serialize_char(char) -> () = "malformed Value";
+        serialize_str(&str) -> () = "malformed Value";
+        serialize_bytes(&[u8]) -> () = "malformed Value";
+        serialize_none() -> () = "malformed Value";
+        serialize_unit() -> () = "malformed Value";
+        serialize_unit_struct(&'static str) -> () = "malformed Value";
+        serialize_unit_variant(&'static str, u32, &'static str) -> () = "malformed Value";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "malformed Value";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "malformed Value";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "malformed Value";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "malformed Value";
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct = "malformed Value";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "malformed Value";
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+}
+
+impl<'a, 'b: 'a> ser::SerializeTuple for EntrySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.position += 1;
+        match self.position {
+            1 => self.map.write_key(value),
+            2 => self.map.write_value(value),
+            _ => Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+        }
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Converts a serialized field of a native struct to the type it is stored as
+///
+/// Floats and doubles are converted into each other, as the type of many fields depends on the
+/// version. Returns `None` if the field has the wrong type.
+fn convert_native_field(package_version: PackageVersion, typ: NativeField, tag: Option<PropertyTag>, field: Vec<u8>) -> Option<Vec<u8>> {
+    let tag = tag?;
+    let double = match typ {
+        NativeField::Real => package_version.ue5 >= VER_UE5_LARGE_WORLD_COORDINATES,
+        NativeField::Float => false,
+        NativeField::Bool => return Some(field).filter(|_| tag.typ == PropertyType::Bool),
+        NativeField::Byte => return Some(field).filter(|_| tag.is_raw_byte()),
+        NativeField::Int => return Some(field).filter(|_| tag.typ == PropertyType::Int),
+        NativeField::Int64 => return Some(field).filter(|_| tag.typ == PropertyType::Int64),
+        NativeField::UInt32 => return Some(field).filter(|_| tag.typ == PropertyType::UInt32),
+        NativeField::NameArray => return Some(field).filter(|_| tag.typ == PropertyType::Array
+            && matches!(tag.inner_type, Some(PropertyType::Name) | Some(PropertyType::Str))),
+        NativeField::Struct(name, _) => return Some(field).filter(|_| tag.struct_name.as_deref() == Some(name)),
+    };
+    let f = match tag.typ {
+        PropertyType::Float => f64::from((&field[..]).read_f32::<LE>().ok()?),
+        PropertyType::Double => (&field[..]).read_f64::<LE>().ok()?,
+        _ => return None,
+    };
+    let mut field = Vec::new();
+    if double {
+        field.write_f64::<LE>(f).ok()?;
+    } else {
+        field.write_f32::<LE>(f as f32).ok()?;
+    }
+    Some(field)
+}
+
+/// Serializes the fields of a struct as properties terminated by `None`
+///
+/// Native structs are written as their raw fields in the order defined by `native_struct_fields`.
+/// Structs with a codec are passed to its encoder as `Property`s, which are parsed back from
+/// their serialized fields.
+struct StructSerializer<'a> {
+    w: &'a mut Vec<u8>,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    name: String,
+    guid: Guid,
+    /// Codec of the struct, which takes precedence over native structs
+    codec: Option<&'a StructCodec>,
+    /// Fields of a struct with a codec
+    codec_fields: Vec<Property>,
+    /// Fields of a native struct in order
+    native_order: Option<&'static [(&'static str, NativeField)]>,
+    /// Serialized fields of a native struct
+    native_fields: Vec<(String, Vec<u8>)>,
+}
+
+impl<'a> StructSerializer<'a> {
+    fn new(w: &'a mut Vec<u8>, package_version: PackageVersion, codecs: &'a StructCodecRegistry, name: String, guid: Guid) -> StructSerializer<'a> {
+        let codec = codecs.get(&name).map(|codec| &**codec);
+        StructSerializer {
+            w,
+            package_version,
+            codecs,
+            codec,
+            codec_fields: Vec::new(),
+            native_order: if codec.is_some() { None } else { native_struct_fields(&name) },
+            name,
+            guid,
+            native_fields: Vec::new(),
+        }
+    }
+
+    fn write_field<T: Serialize + ?Sized>(&mut self, key: &str, array_index: u32, guid: Option<Guid>, value: &T) -> Result<()> {
+        let package_version = self.package_version;
+        let codecs = self.codecs;
+        if self.codec.is_some() {
+            let mut body = Vec::new();
+            let mut tag = match value.serialize(ValueSerializer::new(&mut body, package_version, codecs, key, false))? {
+                Some(tag) => tag,
+                None => return Ok(()),
+            };
+            tag.len = Some(body.len() as u32);
+            let value = parse_value_from(&body, tag, package_version, codecs)?;
+            self.codec_fields.push(Property { name: key.to_string(), array_index, guid, value });
+            return Ok(());
+        }
+        match self.native_order {
+            Some(order) => {
+                let typ = match order.iter().find(|&&(name, _)| name == key) {
+                    Some(&(_, typ)) => typ,
+                    // like unknown properties of other structs, which are ignored when loading
+                    None => return Ok(()),
+                };
+                let mut field = Vec::new();
+                // names are written like the body of an ArrayProperty
+                let in_array = typ != NativeField::NameArray;
+                let mut ser = ValueSerializer::new(&mut field, package_version, codecs, key, in_array);
+                if typ == NativeField::NameArray {
+                    ser.inner = Some(PropertyTag::new(PropertyType::Name, None));
+                }
+                let tag = value.serialize(ser)?;
+                match convert_native_field(package_version, typ, tag, field) {
+                    Some(field) => self.native_fields.push((key.to_string(), field)),
+                    None => return Err(ser::Error::custom(format_args!("field `{}` of native struct `{}` must be {}", key, self.name, typ.description()))),
+                }
+                Ok(())
+            }
+            None => PropertiesSerializer::new(&mut *self.w, package_version, codecs).write_property(key, array_index, guid, value),
+        }
+    }
+
+    fn finish(self) -> Result<Option<PropertyTag>> {
+        if let Some(codec) = self.codec {
+            (codec.encode)(&self.codec_fields, &mut *self.w)?;
+        }
+        match self.native_order {
+            Some(order) => for &(name, _) in order {
+                match self.native_fields.iter().find(|&(field, _)| field == name) {
+                    Some((_, field)) => self.w.write_all(field)?,
+                    None => return Err(ser::Error::custom(format_args!("missing field `{}` of native struct `{}`", name, self.name))),
+                }
+            },
+            None if self.codec.is_none() => write_string(&mut *self.w, "None").map(|_| ())?,
+            None => {},
+        }
+        let mut tag = PropertyTag::new(PropertyType::Struct, None);
+        tag.struct_name = Some(self.name);
+        tag.struct_guid = self.guid;
+        Ok(Some(tag))
+    }
+}
+
+impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.write_field(key, 0, None, value)
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        self.finish()
+    }
+}
+
+/// Serializes the `Property`s of a `Value` of a struct
+impl<'a> ser::SerializeSeq for StructSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(PropertySerializer { parent: self, name: None, array_index: 0, guid: None })
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        self.finish()
+    }
+}
+
+/// Serializes a `Property` of a `Value`, whose name, array index and GUID precede its value
+struct PropertySerializer<'a, 'b: 'a> {
+    parent: &'a mut StructSerializer<'b>,
+    name: Option<String>,
+    array_index: u32,
+    guid: Option<Guid>,
+}
+
+impl<'a, 'b: 'a> ser::Serializer for PropertySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> () = "malformed Value";
+        serialize_i8(i8) -> () = "malformed Value";
+        serialize_i16(i16) -> () = "malformed Value";
+        serialize_i32(i32) -> () = "malformed Value";
+        serialize_i64(i64) -> () = "malformed Value";
+        serialize_u8(u8) -> () = "malformed Value";
+        serialize_u16(u16) -> () = "malformed Value";
+        serialize_u32(u32) -> () = "malformed Value";
+        serialize_u64(u64) -> () = "malformed Value";
+        serialize_f32(f32) -> () = "malformed Value";
+        serialize_f64(f64) -> () = "malformed Value";
+        serialize_char(char) -> () = "malformed Value";
+        serialize_str(&str) -> () = "malformed Value";
+        serialize_bytes(&[u8]) -> () = "malformed Value";
+        serialize_none() -> () = "malformed Value";
+        serialize_unit() -> () = "malformed Value";
+        serialize_unit_struct(&'static str) -> () = "malformed Value";
+        serialize_unit_variant(&'static str, u32, &'static str) -> () = "malformed Value";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "malformed Value";
+        serialize_tuple(usize) -> Self::SerializeTuple = "malformed Value";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "malformed Value";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "malformed Value";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "malformed Value";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "malformed Value";
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<()> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+}
+
+impl<'a, 'b: 'a> ser::SerializeStruct for PropertySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        match key {
+            "name" => self.name = Some(serialize_string(value)?),
+            "array_index" => self.array_index = serialize_u32(value)?,
+            "guid" => self.guid = serialize_guid(value)?,
+            "value" => match self.name {
+                Some(ref name) => self.parent.write_field(name, self.array_index, self.guid, value)?,
+                None => return Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+            },
+            _ => return Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializes the payload of a `Value` whose type needs more than a primitive
+///
+/// The payload is a tuple starting with a type name, which is the enum name of a ByteProperty
+/// holding an enum variant or of an EnumProperty, the element type of an ArrayProperty and the struct type name of a StructProperty.
+/// A property of an unknown type is `(TypeName, Option<InnerTypeName>, Option<ValueTypeName>, [u8])`.
+/// See `value.rs` for the remaining elements.
+struct PayloadSerializer<'a> {
+    ser: ValueSerializer<'a>,
+    typ: PropertyType,
+    /// Number of elements serialized so far
+    position: usize,
+    type_name: String,
+    struct_name: Option<String>,
+    guid: Option<Guid>,
+    /// Element or key type of a property of an unknown type
+    inner_type: Option<String>,
+    /// Value type of a map or of a property of an unknown type
+    value_type: Option<String>,
+    /// Serialized removed keys of a map
+    removed: Vec<u8>,
+    removed_count: u32,
+    tag: Option<PropertyTag>,
+}
+
+impl<'a> PayloadSerializer<'a> {
+    fn new(ser: ValueSerializer<'a>, typ: PropertyType) -> PayloadSerializer<'a> {
+        PayloadSerializer {
+            ser,
+            typ,
+            position: 0,
+            type_name: String::new(),
+            struct_name: None,
+            guid: None,
+            inner_type: None,
+            value_type: None,
+            removed: Vec::new(),
+            removed_count: 0,
+            tag: None,
+        }
+    }
+}
+
+impl<'a> ser::Serializer for PayloadSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+    type SerializeSeq = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleVariant = Impossible<Option<PropertyTag>, Error>;
+    type SerializeMap = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStructVariant = Impossible<Option<PropertyTag>, Error>;
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    /// Raw byte of a ByteProperty, which is the only payload that isn't a tuple
+    fn serialize_u8(self, v: u8) -> Result<Option<PropertyTag>> {
+        if self.typ != PropertyType::Byte {
+            return Err(Error::from(ErrorKind::UnsupportedType("malformed Value")));
+        }
+        ser::Serializer::serialize_u8(self.ser, v)
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> Option<PropertyTag> = "malformed Value";
+        serialize_i8(i8) -> Option<PropertyTag> = "malformed Value";
+        serialize_i16(i16) -> Option<PropertyTag> = "malformed Value";
+        serialize_i32(i32) -> Option<PropertyTag> = "malformed Value";
+        serialize_i64(i64) -> Option<PropertyTag> = "malformed Value";
+        serialize_u16(u16) -> Option<PropertyTag> = "malformed Value";
+        serialize_u32(u32) -> Option<PropertyTag> = "malformed Value";
+        serialize_u64(u64) -> Option<PropertyTag> = "malformed Value";
+        serialize_f32(f32) -> Option<PropertyTag> = "malformed Value";
+        serialize_f64(f64) -> Option<PropertyTag> = "malformed Value";
+        serialize_char(char) -> Option<PropertyTag> = "malformed Value";
+        serialize_str(&str) -> Option<PropertyTag> = "malformed Value";
+        serialize_bytes(&[u8]) -> Option<PropertyTag> = "malformed Value";
+        serialize_none() -> Option<PropertyTag> = "malformed Value";
+        serialize_unit() -> Option<PropertyTag> = "malformed Value";
+        serialize_unit_struct(&'static str) -> Option<PropertyTag> = "malformed Value";
+        serialize_unit_variant(&'static str, u32, &'static str) -> Option<PropertyTag> = "malformed Value";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "malformed Value";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "malformed Value";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "malformed Value";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "malformed Value";
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct = "malformed Value";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "malformed Value";
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+}
+
+impl<'a> ser::SerializeTuple for PayloadSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let position = self.position;
+        self.position += 1;
+        if position == 0 {
+            self.type_name = serialize_string(value)?;
+            return Ok(());
+        }
+        let ser = ValueSerializer::new(&mut *self.ser.w, self.ser.package_version, self.ser.codecs, self.ser.name, self.ser.in_array);
+        match (&self.typ, position) {
+            // (EnumName, Variant)
+            (PropertyType::Byte, 1) | (PropertyType::Enum, 1) => {
+                write_string(&mut *ser.w, &serialize_string(value)?)?;
+                let mut tag = PropertyTag::new(self.typ.clone(), None);
+                tag.enum_name = Some(self.type_name.clone());
+                self.tag = Some(tag);
+            }
+            // (InnerTypeName, Option<StructTypeName>, Option<StructGuid>, [Value])
+            (PropertyType::Array, 1) => self.struct_name = serialize_option_string(value)?,
+            (PropertyType::Array, 2) => self.guid = serialize_guid(value)?,
+            (PropertyType::Array, 3) => {
+                let mut array = ser::Serializer::serialize_seq(ser, None)?;
+                array.inner_type = Some(PropertyType::from_str(&self.type_name).void_unwrap());
+                let guid = self.guid.unwrap_or_default();
+                array.struct_type = self.struct_name.take().map(|name| StructType { name, guid });
+                self.tag = value.serialize(SeqSerializer(SeqBody::Array(array)))?;
+            }
+            // (InnerTypeName, [RemovedElement], [Value])
+            (PropertyType::Set, 1) => {
+                let removed = ElementsSerializer {
+                    w: &mut self.removed,
+                    count: &mut self.removed_count,
+                    package_version: ser.package_version,
+                    codecs: ser.codecs,
+                    name: ser.name,
+                };
+                value.serialize(SeqSerializer(SeqBody::Elements(removed)))?;
+            }
+            (PropertyType::Set, 2) => {
+                let mut set = ser::Serializer::serialize_seq(ValueSerializer { set: true, ..ser }, None)?;
+                set.inner_type = Some(PropertyType::from_str(&self.type_name).void_unwrap());
+                set.removed = mem::take(&mut self.removed);
+                set.removed_count = self.removed_count;
+                self.tag = value.serialize(SeqSerializer(SeqBody::Array(set)))?;
+            }
+            // (StructTypeName, StructGuid, [Property])
+            (PropertyType::Struct, 1) => self.guid = serialize_guid(value)?,
+            (PropertyType::Struct, 2) => {
+                let guid = self.guid.unwrap_or_default();
+                let properties = StructSerializer::new(ser.w, ser.package_version, ser.codecs, self.type_name.clone(), guid);
+                self.tag = value.serialize(SeqSerializer(SeqBody::Struct(properties)))?;
+            }
+            // (KeyTypeName, ValueTypeName, [RemovedKey], [(Key, Value)])
+            (PropertyType::Map, 1) => self.value_type = Some(serialize_string(value)?),
+            (PropertyType::Map, 2) => {
+                let removed = ElementsSerializer {
+                    w: &mut self.removed,
+                    count: &mut self.removed_count,
+                    package_version: ser.package_version,
+                    codecs: ser.codecs,
+                    name: ser.name,
+                };
+                value.serialize(SeqSerializer(SeqBody::Elements(removed)))?;
+            }
+            (PropertyType::Map, 3) => {
+                let mut map = MapSerializer::new(ser.w, ser.package_version, ser.codecs, ser.name);
+                map.removed = mem::take(&mut self.removed);
+                map.removed_count = self.removed_count;
+                map.key = Some(PropertyTag::new(PropertyType::from_str(&self.type_name).void_unwrap(), None));
+                let value_type = self.value_type.as_deref().unwrap_or_default();
+                map.value = Some(PropertyTag::new(PropertyType::from_str(value_type).void_unwrap(), None));
+                self.tag = value.serialize(SeqSerializer(SeqBody::Map(map)))?;
+            }
+            // (TypeName, Option<InnerTypeName>, Option<ValueTypeName>, [u8])
+            (PropertyType::Unknown(_), 1) => self.inner_type = serialize_option_string(value)?,
+            (PropertyType::Unknown(_), 2) => self.value_type = serialize_option_string(value)?,
+            (PropertyType::Unknown(_), 3) => {
+                // the body of a type we don't know is written back as it was read
+                let bytes = Vec::<u8>::deserialize(value.serialize(ContentSerializer)?)?;
+                ser.w.write_all(&bytes)?;
+                let mut tag = PropertyTag::new(PropertyType::from_str(&self.type_name).void_unwrap(), None);
+                tag.inner_type = self.inner_type.take().map(|typ| PropertyType::from_str(&typ).void_unwrap());
+                tag.value_type = self.value_type.take().map(|typ| PropertyType::from_str(&typ).void_unwrap());
+                self.tag = Some(tag);
+            }
+            _ => return Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        match self.tag {
+            Some(tag) => Ok(Some(tag)),
+            None => Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+        }
+    }
+}
+
+/// Serializes a container wrapped by `serde_gvas::typed`, which is a tuple of default elements
+/// followed by the container
+///
+/// The default elements are only serialized to get their tags, which are the element types
+/// of the container.
+struct TypedSerializer<'a> {
+    ser: ValueSerializer<'a>,
+    len: usize,
+    position: usize,
+    tag: Option<PropertyTag>,
+}
+
+impl<'a> ser::Serializer for TypedSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+    type SerializeSeq = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleVariant = Impossible<Option<PropertyTag>, Error>;
+    type SerializeMap = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStructVariant = Impossible<Option<PropertyTag>, Error>;
+
+    fn serialize_tuple(self, len: usize) -> Result<Self> {
+        Ok(TypedSerializer { len, ..self })
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> Option<PropertyTag> = "malformed typed container";
+        serialize_i8(i8) -> Option<PropertyTag> = "malformed typed container";
+        serialize_i16(i16) -> Option<PropertyTag> = "malformed typed container";
+        serialize_i32(i32) -> Option<PropertyTag> = "malformed typed container";
+        serialize_i64(i64) -> Option<PropertyTag> = "malformed typed container";
+        serialize_u8(u8) -> Option<PropertyTag> = "malformed typed container";
+        serialize_u16(u16) -> Option<PropertyTag> = "malformed typed container";
+        serialize_u32(u32) -> Option<PropertyTag> = "malformed typed container";
+        serialize_u64(u64) -> Option<PropertyTag> = "malformed typed container";
+        serialize_f32(f32) -> Option<PropertyTag> = "malformed typed container";
+        serialize_f64(f64) -> Option<PropertyTag> = "malformed typed container";
+        serialize_char(char) -> Option<PropertyTag> = "malformed typed container";
+        serialize_str(&str) -> Option<PropertyTag> = "malformed typed container";
+        serialize_bytes(&[u8]) -> Option<PropertyTag> = "malformed typed container";
+        serialize_none() -> Option<PropertyTag> = "malformed typed container";
+        serialize_unit() -> Option<PropertyTag> = "malformed typed container";
+        serialize_unit_struct(&'static str) -> Option<PropertyTag> = "malformed typed container";
+        serialize_unit_variant(&'static str, u32, &'static str) -> Option<PropertyTag> = "malformed typed container";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "malformed typed container";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "malformed typed container";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "malformed typed container";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "malformed typed container";
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct = "malformed typed container";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "malformed typed container";
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed typed container")))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed typed container")))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed typed container")))
+    }
+}
+
+impl<'a> ser::SerializeTuple for TypedSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let position = self.position;
+        self.position += 1;
+        let ser = &mut self.ser;
+        if position + 1 < self.len {
+            // a default element, which is serialized like an element of the container
+            let mut element = Vec::new();
+            let tag = value.serialize(ValueSerializer::new(&mut element, ser.package_version, ser.codecs, ser.name, true))?;
+            match position {
+                0 => ser.inner = tag,
+                _ => ser.value = tag,
+            }
+            return Ok(());
+        }
+        let container = ValueSerializer {
+            w: &mut *ser.w,
+            inner: ser.inner.take(),
+            value: ser.value.take(),
+            ..*ser
+        };
+        self.tag = value.serialize(container)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        Ok(self.tag)
+    }
+}
+
+/// Body of an ArrayProperty, SetProperty, StructProperty or MapProperty contained in a `Value`,
+/// which is a sequence of elements, properties or entries
+enum SeqBody<'a> {
+    Array(ArraySerializer<'a>),
+    Struct(StructSerializer<'a>),
+    Map(MapSerializer<'a>),
+    Elements(ElementsSerializer<'a>),
+}
+
+/// Serializes values without tags, e.g. the removed keys of a map
+struct ElementsSerializer<'a> {
+    w: &'a mut Vec<u8>,
+    count: &'a mut u32,
+    package_version: PackageVersion,
+    codecs: &'a StructCodecRegistry,
+    name: &'a str,
+}
+
+/// Serializes the body of a compound property contained in a `Value`
+struct SeqSerializer<'a>(SeqBody<'a>);
+
+impl<'a> ser::Serializer for SeqSerializer<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+    type SerializeSeq = SeqBody<'a>;
+    type SerializeTuple = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeTupleVariant = Impossible<Option<PropertyTag>, Error>;
+    type SerializeMap = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStruct = Impossible<Option<PropertyTag>, Error>;
+    type SerializeStructVariant = Impossible<Option<PropertyTag>, Error>;
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqBody<'a>> {
+        Ok(self.0)
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> Option<PropertyTag> = "malformed Value";
+        serialize_i8(i8) -> Option<PropertyTag> = "malformed Value";
+        serialize_i16(i16) -> Option<PropertyTag> = "malformed Value";
+        serialize_i32(i32) -> Option<PropertyTag> = "malformed Value";
+        serialize_i64(i64) -> Option<PropertyTag> = "malformed Value";
+        serialize_u8(u8) -> Option<PropertyTag> = "malformed Value";
+        serialize_u16(u16) -> Option<PropertyTag> = "malformed Value";
+        serialize_u32(u32) -> Option<PropertyTag> = "malformed Value";
+        serialize_u64(u64) -> Option<PropertyTag> = "malformed Value";
+        serialize_f32(f32) -> Option<PropertyTag> = "malformed Value";
+        serialize_f64(f64) -> Option<PropertyTag> = "malformed Value";
+        serialize_char(char) -> Option<PropertyTag> = "malformed Value";
+        serialize_str(&str) -> Option<PropertyTag> = "malformed Value";
+        serialize_bytes(&[u8]) -> Option<PropertyTag> = "malformed Value";
+        serialize_none() -> Option<PropertyTag> = "malformed Value";
+        serialize_unit() -> Option<PropertyTag> = "malformed Value";
+        serialize_unit_struct(&'static str) -> Option<PropertyTag> = "malformed Value";
+        serialize_unit_variant(&'static str, u32, &'static str) -> Option<PropertyTag> = "malformed Value";
+        serialize_tuple(usize) -> Self::SerializeTuple = "malformed Value";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "malformed Value";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "malformed Value";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "malformed Value";
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct = "malformed Value";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "malformed Value";
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<Option<PropertyTag>> {
+        Err(Error::from(ErrorKind::UnsupportedType("malformed Value")))
+    }
+}
+
+impl<'a> ser::SerializeSeq for SeqBody<'a> {
+    type Ok = Option<PropertyTag>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match self {
+            SeqBody::Array(array) => array.serialize_element(value),
+            SeqBody::Struct(properties) => ser::SerializeSeq::serialize_element(properties, value),
+            SeqBody::Map(map) => ser::SerializeSeq::serialize_element(map, value),
+            SeqBody::Elements(elements) => {
+                value.serialize(ValueSerializer::new(&mut *elements.w, elements.package_version, elements.codecs, elements.name, true))?;
+                *elements.count += 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn end(self) -> Result<Option<PropertyTag>> {
+        match self {
+            SeqBody::Array(array) => ser::SerializeSeq::end(array),
+            SeqBody::Struct(properties) => ser::SerializeSeq::end(properties),
+            SeqBody::Map(map) => ser::SerializeSeq::end(map),
+            SeqBody::Elements(_) => Ok(None),
+        }
+    }
+}
+
+/// Serializes a string, a u32 or an `Option` of one of them, which are used for keys of maps
+/// and the parts of a `Value` that aren't values themselves
+struct ScalarSerializer;
+
+enum Scalar {
+    None,
+    Str(String),
+    U32(u32),
+}
+
+fn serialize_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    match value.serialize(ScalarSerializer)? {
+        Scalar::Str(s) => Ok(s),
+        _ => Err(Error::from(ErrorKind::UnsupportedType("non-string key or type name"))),
+    }
+}
+
+fn serialize_option_string<T: Serialize + ?Sized>(value: &T) -> Result<Option<String>> {
+    match value.serialize(ScalarSerializer)? {
+        Scalar::None => Ok(None),
+        Scalar::Str(s) => Ok(Some(s)),
+        _ => Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+    }
+}
+
+fn serialize_u32<T: Serialize + ?Sized>(value: &T) -> Result<u32> {
+    match value.serialize(ScalarSerializer)? {
+        Scalar::U32(u) => Ok(u),
+        _ => Err(Error::from(ErrorKind::UnsupportedType("malformed Value"))),
+    }
+}
+
+fn serialize_guid<T: Serialize + ?Sized>(value: &T) -> Result<Option<Guid>> {
+    serialize_option_string(value)?.map(|guid| guid.parse()).transpose()
+}
+
+impl ser::Serializer for ScalarSerializer {
+    type Ok = Scalar;
+    type Error = Error;
+    type SerializeSeq = Impossible<Scalar, Error>;
+    type SerializeTuple = Impossible<Scalar, Error>;
+    type SerializeTupleStruct = Impossible<Scalar, Error>;
+    type SerializeTupleVariant = Impossible<Scalar, Error>;
+    type SerializeMap = Impossible<Scalar, Error>;
+    type SerializeStruct = Impossible<Scalar, Error>;
+    type SerializeStructVariant = Impossible<Scalar, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<Scalar> {
+        Ok(Scalar::Str(v.to_string()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Scalar> {
+        Ok(Scalar::Str(v.to_string()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Scalar> {
+        Ok(Scalar::U32(v))
+    }
+
+    fn serialize_none(self) -> Result<Scalar> {
+        Ok(Scalar::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Scalar> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Scalar> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Scalar> {
+        Ok(Scalar::Str(variant.to_string()))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> Scalar = "non-string key or type name";
+        serialize_i8(i8) -> Scalar = "non-string key or type name";
+        serialize_i16(i16) -> Scalar = "non-string key or type name";
+        serialize_i32(i32) -> Scalar = "non-string key or type name";
+        serialize_i64(i64) -> Scalar = "non-string key or type name";
+        serialize_u8(u8) -> Scalar = "non-string key or type name";
+        serialize_u16(u16) -> Scalar = "non-string key or type name";
+        serialize_u64(u64) -> Scalar = "non-string key or type name";
+        serialize_f32(f32) -> Scalar = "non-string key or type name";
+        serialize_f64(f64) -> Scalar = "non-string key or type name";
+        serialize_bytes(&[u8]) -> Scalar = "non-string key or type name";
+        serialize_unit() -> Scalar = "non-string key or type name";
+        serialize_unit_struct(&'static str) -> Scalar = "non-string key or type name";
+        serialize_seq(Option<usize>) -> Self::SerializeSeq = "non-string key or type name";
+        serialize_tuple(usize) -> Self::SerializeTuple = "non-string key or type name";
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct = "non-string key or type name";
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant = "non-string key or type name";
+        serialize_map(Option<usize>) -> Self::SerializeMap = "non-string key or type name";
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct = "non-string key or type name";
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant = "non-string key or type name";
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<Scalar> {
+        Err(Error::from(ErrorKind::UnsupportedType("non-string key or type name")))
+    }
+}
+
+/// Tag of a StructProperty written by one of the types of this crate
+fn struct_tag(name: &str) -> PropertyTag {
+    let mut tag = PropertyTag::new(PropertyType::Struct, None);
+    tag.struct_name = Some(name.to_string());
+    tag
+}
+
+/// Writes the tag of a property, i.e. everything between its name and its value
+fn write_tag<W: Write>(mut w: W, package_version: PackageVersion, tag: &PropertyTag) -> Result<()> {
+    trace!("write_tag: {:?}", tag);
+    write_string(&mut w, tag.typ.as_str())?;
+    w.write_u32::<LE>(tag.len.unwrap_or(0))?;
+    w.write_u32::<LE>(tag.array_index)?;
+    match tag.typ {
+        PropertyType::Struct => {
+            write_string(&mut w, tag.struct_name.as_deref().unwrap_or(""))?;
+            if package_version.ue4 >= VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG {
+                w.write_all(&tag.struct_guid.0)?;
+            }
+        }
+        PropertyType::Bool => w.write_u8(tag.bool_value.unwrap_or(false) as u8)?,
+        PropertyType::Byte | PropertyType::Enum => write_string(&mut w, tag.enum_name.as_deref().unwrap_or("None")).map(|_| ())?,
+        PropertyType::Array | PropertyType::Set => write_string(&mut w, tag.inner_type.as_ref().map_or("None", PropertyType::as_str)).map(|_| ())?,
+        PropertyType::Map => {
+            write_string(&mut w, tag.inner_type.as_ref().map_or("None", PropertyType::as_str))?;
+            write_string(&mut w, tag.value_type.as_ref().map_or("None", PropertyType::as_str))?;
+        }
+        _ => {}
+    }
+    if package_version.ue4 >= VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG {
+        match tag.guid {
+            Some(guid) => {
+                w.write_u8(1)?;
+                w.write_all(&guid.0)?;
+            }
+            None => w.write_u8(0)?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes a delegate, which is the body of a DelegateProperty and the element of multicast delegates
+fn write_delegate<W: Write>(w: &mut W, delegate: &Delegate) -> Result<()> {
+    write_string(&mut *w, &delegate.object)?;
+    write_string(&mut *w, &delegate.function_name)?;
+    Ok(())
+}
+
+/// Writes the body of a SoftObjectProperty or SoftClassProperty, i.e. an `FSoftObjectPath`
+fn write_soft_object_path<W: Write>(w: &mut W, package_version: PackageVersion, path: &SoftObjectPath) -> Result<()> {
+    if package_version.ue5 >= VER_UE5_FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES {
+        let (package_name, asset_name) = path.package_and_asset_name();
+        write_string(&mut *w, package_name)?;
+        write_string(&mut *w, asset_name)?;
+    } else {
+        write_string(&mut *w, &path.asset_path_name)?;
+    }
+    write_string(&mut *w, &path.sub_path_string)?;
+    Ok(())
+}
+
+/// Writes the body of a TextProperty, i.e. an `FText`
+fn write_text<W: Write>(w: &mut W, text: &FText) -> Result<()> {
+    w.write_u32::<LE>(text.flags)?;
+    match &text.history {
+        TextHistory::None { culture_invariant_string } => {
+            w.write_i8(-1)?;
+            w.write_u32::<LE>(culture_invariant_string.is_some() as u32)?;
+            if let Some(s) = culture_invariant_string {
+                write_string(&mut *w, s)?;
+            }
+        }
+        TextHistory::Base { namespace, key, source_string } => {
+            w.write_i8(0)?;
+            write_string(&mut *w, namespace)?;
+            write_string(&mut *w, key)?;
+            write_string(&mut *w, source_string)?;
+        }
+        TextHistory::NamedFormat { source_format, arguments } => {
+            w.write_i8(1)?;
+            write_text(w, source_format)?;
+            write_named_format_arguments(w, arguments)?;
+        }
+        TextHistory::OrderedFormat { source_format, arguments } => {
+            w.write_i8(2)?;
+            write_text(w, source_format)?;
+            w.write_u32::<LE>(arguments.len() as u32)?;
+            for argument in arguments {
+                write_format_argument(w, argument)?;
+            }
+        }
+        TextHistory::ArgumentFormat { source_format, arguments } => {
+            w.write_i8(3)?;
+            write_text(w, source_format)?;
+            w.write_u32::<LE>(arguments.len() as u32)?;
+            for (name, argument) in arguments {
+                write_format_argument_data(w, name, argument)?;
+            }
+        }
+        TextHistory::AsNumber { source_value, format_options, target_culture } => {
+            w.write_i8(4)?;
+            write_format_argument(w, source_value)?;
+            write_number_formatting_options(w, format_options.as_ref())?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::AsPercent { source_value, format_options, target_culture } => {
+            w.write_i8(5)?;
+            write_format_argument(w, source_value)?;
+            write_number_formatting_options(w, format_options.as_ref())?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::AsCurrency { currency_code, source_value, format_options, target_culture } => {
+            w.write_i8(6)?;
+            write_string(&mut *w, currency_code)?;
+            write_format_argument(w, source_value)?;
+            write_number_formatting_options(w, format_options.as_ref())?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::AsDate { source_date_time, date_style, time_zone, target_culture } => {
+            w.write_i8(7)?;
+            w.write_i64::<LE>(*source_date_time)?;
+            w.write_i8(*date_style)?;
+            write_string(&mut *w, time_zone)?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::AsTime { source_date_time, time_style, time_zone, target_culture } => {
+            w.write_i8(8)?;
+            w.write_i64::<LE>(*source_date_time)?;
+            w.write_i8(*time_style)?;
+            write_string(&mut *w, time_zone)?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::AsDateTime { source_date_time, date_style, time_style, time_zone, target_culture } => {
+            w.write_i8(9)?;
+            w.write_i64::<LE>(*source_date_time)?;
+            w.write_i8(*date_style)?;
+            w.write_i8(*time_style)?;
+            write_string(&mut *w, time_zone)?;
+            write_string(&mut *w, target_culture)?;
+        }
+        TextHistory::Transform { source_text, transform_type } => {
+            w.write_i8(10)?;
+            write_text(w, source_text)?;
+            w.write_u8(*transform_type)?;
+        }
+        TextHistory::StringTableEntry { table_id, key } => {
+            w.write_i8(11)?;
+            write_string(&mut *w, table_id)?;
+            write_string(&mut *w, key)?;
+        }
+        TextHistory::TextGenerator { generator_type, contents } => {
+            w.write_i8(12)?;
+            write_string(&mut *w, generator_type)?;
+            if generator_type != "None" {
+                w.write_u32::<LE>(contents.len() as u32)?;
+                w.write_all(contents)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_named_format_arguments<W: Write>(w: &mut W, arguments: &[(String, FormatArgumentValue)]) -> Result<()> {
+    w.write_u32::<LE>(arguments.len() as u32)?;
+    for (name, argument) in arguments {
+        write_string(&mut *w, name)?;
+        write_format_argument(w, argument)?;
+    }
+    Ok(())
+}
+
+fn write_format_argument<W: Write>(w: &mut W, argument: &FormatArgumentValue) -> Result<()> {
+    match argument {
+        FormatArgumentValue::Int(i) => {
+            w.write_i8(0)?;
+            w.write_i64::<LE>(*i)?;
+        }
+        FormatArgumentValue::UInt(i) => {
+            w.write_i8(1)?;
+            w.write_u64::<LE>(*i)?;
+        }
+        FormatArgumentValue::Float(f) => {
+            w.write_i8(2)?;
+            w.write_f32::<LE>(*f)?;
+        }
+        FormatArgumentValue::Double(f) => {
+            w.write_i8(3)?;
+            w.write_f64::<LE>(*f)?;
+        }
+        FormatArgumentValue::Text(text) => {
+            w.write_i8(4)?;
+            write_text(w, text)?;
+        }
+        FormatArgumentValue::Gender(gender) => {
+            w.write_i8(5)?;
+            w.write_u8(*gender)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes an argument of an ArgumentFormat text history, whose integers are int32
+fn write_format_argument_data<W: Write>(w: &mut W, name: &str, argument: &FormatArgumentValue) -> Result<()> {
+    write_string(&mut *w, name)?;
+    match argument {
+        FormatArgumentValue::Int(i) => {
+            let i = match i32::try_from(*i) {
+                Ok(i) => i,
+                Err(_) => return Err(ser::Error::custom(format_args!("argument `{}` of ArgumentFormat text must fit into an i32", name))),
+            };
+            w.write_u8(0)?;
+            w.write_i32::<LE>(i)?;
+        }
+        FormatArgumentValue::Float(f) => {
+            w.write_u8(2)?;
+            w.write_f32::<LE>(*f)?;
+        }
+        FormatArgumentValue::Text(text) => {
+            w.write_u8(4)?;
+            write_text(w, text)?;
+        }
+        FormatArgumentValue::Gender(gender) => {
+            w.write_u8(5)?;
+            w.write_u8(*gender)?;
+        }
+        FormatArgumentValue::UInt(_) | FormatArgumentValue::Double(_) => {
+            return Err(ser::Error::custom(format_args!("argument `{}` of ArgumentFormat text can't be UInt or Double", name)));
+        }
+    }
+    Ok(())
+}
+
+fn write_number_formatting_options<W: Write>(w: &mut W, options: Option<&NumberFormattingOptions>) -> Result<()> {
+    w.write_u32::<LE>(options.is_some() as u32)?;
+    if let Some(options) = options {
+        w.write_u32::<LE>(options.always_sign as u32)?;
+        w.write_u32::<LE>(options.use_grouping as u32)?;
+        w.write_i8(options.rounding_mode)?;
+        w.write_i32::<LE>(options.minimum_integral_digits)?;
+        w.write_i32::<LE>(options.maximum_integral_digits)?;
+        w.write_i32::<LE>(options.minimum_fractional_digits)?;
+        w.write_i32::<LE>(options.maximum_fractional_digits)?;
+    }
+    Ok(())
+}
+
+/// Writes a string, which is encoded as ASCII if possible and as UTF-16 otherwise
+///
+/// The encoding of strings isn't kept when reading them, so strings stored differently are
+/// written back in this form, e.g. ASCII stored as UTF-16, other Windows-1252 characters stored
+/// as single bytes or an empty string stored as its terminator. Files containing such strings
+/// aren't written back byte for byte.
+///
+/// Returns the number of bytes written.
+pub(crate) fn write_string<W: Write>(mut w: W, s: &str) -> Result<usize> {
+    if s.is_empty() {
+        w.write_i32::<LE>(0)?;
+        Ok(4)
+    } else if s.is_ascii() {
+        w.write_i32::<LE>(s.len() as i32 + 1)?;
+        w.write_all(s.as_bytes())?;
+        w.write_u8(0)?;
+        Ok(s.len() + 5)
+    } else {
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        w.write_i32::<LE>(-(utf16.len() as i32 + 1))?;
+        for &c in &utf16 {
+            w.write_u16::<LE>(c)?;
+        }
+        w.write_u16::<LE>(0)?;
+        Ok(utf16.len() * 2 + 6)
+    }
+}
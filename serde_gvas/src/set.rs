@@ -0,0 +1,41 @@
+//! Serialize a sequence as SetProperty instead of ArrayProperty
+//!
+//! Serde doesn't distinguish sets from other sequences, so fields holding a `HashSet` or
+//! `BTreeSet` are written as ArrayProperty by default. Use this module to write them as
+//! SetProperty instead:
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_gvas;
+//!
+//! use std::collections::HashSet;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct SaveGame {
+//!     #[serde(with = "serde_gvas::set")]
+//!     unlocked_items: HashSet<String>,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Empty sets are written with IntProperty elements, unless their element type is given with
+//! `serde_gvas::typed::set`.
+//!
+//! Deserializing doesn't need this module, as both SetProperty and ArrayProperty can be
+//! deserialized into any sequence.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Name of the newtype struct wrapping sequences that are written as SetProperty
+pub(crate) const SET_TOKEN: &str = "$serde_gvas::Set";
+
+/// Serialize the given sequence as SetProperty
+pub fn serialize<T: Serialize + ?Sized, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_newtype_struct(SET_TOKEN, value)
+}
+
+/// Deserialize a sequence from a SetProperty or ArrayProperty
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+    T::deserialize(d)
+}
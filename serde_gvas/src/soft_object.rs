@@ -0,0 +1,92 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct `SoftObjectPath` is (de)serialized as
+///
+/// It allows the Deserializer to pass both parts of the path to `SoftObjectPath`, while passing
+/// the whole path as string to all other types.
+pub(crate) const SOFT_OBJECT_PATH_TOKEN: &str = "$serde_gvas::SoftObjectPath";
+
+/// Path of an asset as stored in a SoftObjectProperty or SoftClassProperty, i.e. Unreal's
+/// `FSoftObjectPath`
+///
+/// Such a property can be deserialized into a `String`, which results in the string returned
+/// by `Display`, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SoftObjectPath {
+    /// Path of the asset including its package, e.g. `/Game/Maps/Level.Level`
+    ///
+    /// It is `None` for a path not pointing to anything.
+    pub asset_path_name: String,
+    /// Path of an object within the asset, which is usually empty
+    pub sub_path_string: String,
+}
+
+impl SoftObjectPath {
+    /// Splits the asset path into package name and asset name, which is how UE5 stores it
+    ///
+    /// The asset name is `None` if the path only contains a package.
+    pub(crate) fn package_and_asset_name(&self) -> (&str, &str) {
+        match self.asset_path_name.find('.') {
+            Some(i) => (&self.asset_path_name[..i], &self.asset_path_name[i + 1..]),
+            None => (&self.asset_path_name, "None"),
+        }
+    }
+
+    /// Joins package name and asset name as stored by UE5 into an asset path
+    pub(crate) fn from_package_and_asset_name(package_name: String, asset_name: &str, sub_path_string: String) -> SoftObjectPath {
+        let asset_path_name = match asset_name {
+            "None" => package_name,
+            asset_name => format!("{}.{}", package_name, asset_name),
+        };
+        SoftObjectPath { asset_path_name, sub_path_string }
+    }
+}
+
+/// Formats the path like Unreal does, separating the sub path with a colon
+impl fmt::Display for SoftObjectPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.asset_path_name)?;
+        if !self.sub_path_string.is_empty() {
+            write!(f, ":{}", self.sub_path_string)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fields of `SoftObjectPath`, which is a newtype struct around them
+#[derive(Serialize, Deserialize)]
+struct SoftObjectPathFields<S> {
+    asset_path_name: S,
+    sub_path_string: S,
+}
+
+impl Serialize for SoftObjectPath {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let fields = SoftObjectPathFields { asset_path_name: &self.asset_path_name, sub_path_string: &self.sub_path_string };
+        s.serialize_newtype_struct(SOFT_OBJECT_PATH_TOKEN, &fields)
+    }
+}
+
+impl<'de> Deserialize<'de> for SoftObjectPath {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<SoftObjectPath, D::Error> {
+        d.deserialize_newtype_struct(SOFT_OBJECT_PATH_TOKEN, SoftObjectPathVisitor)
+    }
+}
+
+struct SoftObjectPathVisitor;
+
+impl<'de> Visitor<'de> for SoftObjectPathVisitor {
+    type Value = SoftObjectPath;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a SoftObjectProperty")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<SoftObjectPath, D::Error> {
+        let fields: SoftObjectPathFields<String> = SoftObjectPathFields::deserialize(d)?;
+        Ok(SoftObjectPath { asset_path_name: fields.asset_path_name, sub_path_string: fields.sub_path_string })
+    }
+}
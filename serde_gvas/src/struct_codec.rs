@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use error::Result;
+use value::Property;
+
+/// Decodes the binary body of a struct into its fields
+///
+/// The reader starts at the body. If the length of the body is known, reading past it fails,
+/// and so does reading less than all of it.
+pub type StructDecoder = dyn Fn(&mut dyn Read) -> Result<Vec<Property>> + Send + Sync;
+
+/// Encodes the fields of a struct into its binary body
+///
+/// The fields are passed as `Property`s, no matter whether they come from a `Value` or another
+/// type, so they can be inspected like those of a deserialized struct.
+pub type StructEncoder = dyn Fn(&[Property], &mut dyn Write) -> Result<()> + Send + Sync;
+
+pub(crate) struct StructCodec {
+    pub(crate) decode: Box<StructDecoder>,
+    pub(crate) encode: Box<StructEncoder>,
+}
+
+/// Codecs of structs whose body is binary instead of a list of properties, looked up by struct type name
+///
+/// The Deserializer and Serializer consult it before the native structs known to this crate,
+/// so game specific structs can be added and built-in ones replaced. Decoded structs are
+/// represented like other structs, i.e. as `Value::Struct` or deserialized from their fields.
+///
+/// ```
+/// use std::io::{Read, Write};
+/// use serde_gvas::{Property, StructCodecRegistry, Value};
+///
+/// let mut codecs = StructCodecRegistry::new();
+/// codecs.register("CharacterDNA", |r: &mut dyn Read| {
+///     let mut seed = [0u8; 16];
+///     r.read_exact(&mut seed)?;
+///     Ok(vec![Property::new("Seed", Value::Str(format!("{:?}", seed)))])
+/// }, |_fields: &[Property], w: &mut dyn Write| {
+///     w.write_all(&[0u8; 16])?;
+///     Ok(())
+/// });
+/// assert!(codecs.contains("CharacterDNA"));
+/// ```
+#[derive(Clone, Default)]
+pub struct StructCodecRegistry {
+    codecs: HashMap<String, Arc<StructCodec>>,
+}
+
+impl StructCodecRegistry {
+    /// Creates a registry without any codecs
+    pub fn new() -> StructCodecRegistry {
+        StructCodecRegistry::default()
+    }
+
+    /// Registers the codec of the struct with the given type name, replacing any previous one
+    pub fn register<D, E>(&mut self, struct_name: &str, decode: D, encode: E)
+    where
+        D: Fn(&mut dyn Read) -> Result<Vec<Property>> + Send + Sync + 'static,
+        E: Fn(&[Property], &mut dyn Write) -> Result<()> + Send + Sync + 'static,
+    {
+        let codec = StructCodec { decode: Box::new(decode), encode: Box::new(encode) };
+        self.codecs.insert(struct_name.to_string(), Arc::new(codec));
+    }
+
+    /// Removes the codec of the struct with the given type name, returning whether there was one
+    pub fn unregister(&mut self, struct_name: &str) -> bool {
+        self.codecs.remove(struct_name).is_some()
+    }
+
+    /// Returns whether there is a codec for the struct with the given type name
+    pub fn contains(&self, struct_name: &str) -> bool {
+        self.codecs.contains_key(struct_name)
+    }
+
+    pub(crate) fn get(&self, struct_name: &str) -> Option<&Arc<StructCodec>> {
+        self.codecs.get(struct_name)
+    }
+}
+
+impl fmt::Debug for StructCodecRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.codecs.keys()).finish()
+    }
+}
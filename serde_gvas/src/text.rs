@@ -0,0 +1,172 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct `FText` is (de)serialized as
+///
+/// It allows the Deserializer to pass all fields of a TextProperty to `FText`, while passing
+/// only its string to all other types.
+pub(crate) const TEXT_TOKEN: &str = "$serde_gvas::Text";
+
+/// Localizable text as stored in a TextProperty, i.e. Unreal's `FText`
+///
+/// A TextProperty can be deserialized into a `String`, which results in the string returned by
+/// `Display`. Deserialize it into `FText` instead to keep everything needed to write it back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FText {
+    pub flags: u32,
+    pub history: TextHistory,
+}
+
+/// How a text was created, which is needed to localize it
+///
+/// The type of the history is stored as i8, where `None` is -1 and all other variants are
+/// numbered from 0 in the order they are declared.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TextHistory {
+    /// Text which isn't localized, e.g. created with `FText::FromString`
+    None { culture_invariant_string: Option<String> },
+    /// Text identified by namespace and key within the localization data
+    Base { namespace: String, key: String, source_string: String },
+    NamedFormat { source_format: Box<FText>, arguments: Vec<(String, FormatArgumentValue)> },
+    OrderedFormat { source_format: Box<FText>, arguments: Vec<FormatArgumentValue> },
+    /// Text formatted with named arguments of an `FFormatArgumentData`
+    ///
+    /// Its arguments are stored differently from the other histories: the type is a u8, integers
+    /// are i32 and there are no `UInt` and `Double` arguments.
+    ArgumentFormat { source_format: Box<FText>, arguments: Vec<(String, FormatArgumentValue)> },
+    AsNumber {
+        source_value: FormatArgumentValue,
+        format_options: Option<NumberFormattingOptions>,
+        target_culture: String,
+    },
+    AsPercent {
+        source_value: FormatArgumentValue,
+        format_options: Option<NumberFormattingOptions>,
+        target_culture: String,
+    },
+    AsCurrency {
+        currency_code: String,
+        source_value: FormatArgumentValue,
+        format_options: Option<NumberFormattingOptions>,
+        target_culture: String,
+    },
+    /// Date of the given ticks, i.e. 100 nanoseconds since 0001-01-01
+    AsDate { source_date_time: i64, date_style: i8, time_zone: String, target_culture: String },
+    /// Time of the given ticks, i.e. 100 nanoseconds since 0001-01-01
+    AsTime { source_date_time: i64, time_style: i8, time_zone: String, target_culture: String },
+    /// Date and time of the given ticks, i.e. 100 nanoseconds since 0001-01-01
+    AsDateTime {
+        source_date_time: i64,
+        date_style: i8,
+        time_style: i8,
+        time_zone: String,
+        target_culture: String,
+    },
+    /// Text transformed to lower case (0) or upper case (1)
+    Transform { source_text: Box<FText>, transform_type: u8 },
+    StringTableEntry { table_id: String, key: String },
+    /// Text generated by a registered generator, whose serialized contents are kept as bytes
+    ///
+    /// The contents are only present if the generator type isn't `None`.
+    TextGenerator { generator_type: String, contents: Vec<u8> },
+}
+
+/// Argument of a formatted text
+///
+/// The type of the argument is stored as i8, numbered from 0 in the order the variants are declared.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FormatArgumentValue {
+    Int(i64),
+    UInt(u64),
+    Float(f32),
+    Double(f64),
+    Text(Box<FText>),
+    Gender(u8),
+}
+
+/// Options of a text formatted as number
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NumberFormattingOptions {
+    pub always_sign: bool,
+    pub use_grouping: bool,
+    pub rounding_mode: i8,
+    pub minimum_integral_digits: i32,
+    pub maximum_integral_digits: i32,
+    pub minimum_fractional_digits: i32,
+    pub maximum_fractional_digits: i32,
+}
+
+/// Formats the source string of the text without localizing it
+///
+/// Formatted texts are represented by their source format and numbers and dates by their
+/// source value, as formatting them would require the localization data of the game.
+impl fmt::Display for FText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.history {
+            TextHistory::None { culture_invariant_string } =>
+                f.write_str(culture_invariant_string.as_ref().map_or("", String::as_str)),
+            TextHistory::Base { source_string, .. } => f.write_str(source_string),
+            TextHistory::NamedFormat { source_format, .. }
+            | TextHistory::OrderedFormat { source_format, .. }
+            | TextHistory::ArgumentFormat { source_format, .. } => source_format.fmt(f),
+            TextHistory::AsNumber { source_value, .. }
+            | TextHistory::AsPercent { source_value, .. }
+            | TextHistory::AsCurrency { source_value, .. } => source_value.fmt(f),
+            TextHistory::AsDate { source_date_time, .. }
+            | TextHistory::AsTime { source_date_time, .. }
+            | TextHistory::AsDateTime { source_date_time, .. } => source_date_time.fmt(f),
+            TextHistory::Transform { source_text, .. } => source_text.fmt(f),
+            TextHistory::StringTableEntry { key, .. } => f.write_str(key),
+            TextHistory::TextGenerator { .. } => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for FormatArgumentValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatArgumentValue::Int(i) => i.fmt(f),
+            FormatArgumentValue::UInt(i) => i.fmt(f),
+            FormatArgumentValue::Float(x) => x.fmt(f),
+            FormatArgumentValue::Double(x) => x.fmt(f),
+            FormatArgumentValue::Text(text) => text.fmt(f),
+            FormatArgumentValue::Gender(gender) => gender.fmt(f),
+        }
+    }
+}
+
+/// Fields of `FText`, which is a newtype struct around them
+#[derive(Serialize, Deserialize)]
+struct TextFields<H> {
+    flags: u32,
+    history: H,
+}
+
+impl Serialize for FText {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(TEXT_TOKEN, &TextFields { flags: self.flags, history: &self.history })
+    }
+}
+
+impl<'de> Deserialize<'de> for FText {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<FText, D::Error> {
+        d.deserialize_newtype_struct(TEXT_TOKEN, TextVisitor)
+    }
+}
+
+struct TextVisitor;
+
+impl<'de> Visitor<'de> for TextVisitor {
+    type Value = FText;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a TextProperty")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<FText, D::Error> {
+        let fields: TextFields<TextHistory> = TextFields::deserialize(d)?;
+        Ok(FText { flags: fields.flags, history: fields.history })
+    }
+}
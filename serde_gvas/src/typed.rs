@@ -0,0 +1,144 @@
+//! Serialize a container along with its element types, so that it keeps them when empty
+//!
+//! The type of an ArrayProperty, SetProperty or MapProperty includes the type of its elements,
+//! which serde only reveals by serializing an element. Empty containers are therefore written
+//! with IntProperty elements by default. Use this module to derive the element types from a
+//! default element instead:
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_gvas;
+//!
+//! use std::collections::{HashMap, HashSet};
+//!
+//! #[derive(Default, Serialize, Deserialize)]
+//! struct Item {
+//!     count: i32,
+//! }
+//!
+//! #[derive(Default, Serialize, Deserialize)]
+//! struct SaveGame {
+//!     #[serde(with = "serde_gvas::typed")]
+//!     inventory: Vec<Item>,
+//!     #[serde(with = "serde_gvas::typed")]
+//!     counters: HashMap<String, i32>,
+//!     #[serde(with = "serde_gvas::typed::set")]
+//!     unlocked_items: HashSet<String>,
+//! }
+//!
+//! # fn main() {
+//! let file = std::fs::read("../files/flai.sav").unwrap();
+//! let header = serde_gvas::GvasHeader::read(&file[..]).unwrap();
+//! let bytes = serde_gvas::to_vec(&header, &SaveGame::default()).unwrap();
+//! let save_game: SaveGame = serde_gvas::from_slice(&bytes).unwrap();
+//! assert!(save_game.inventory.is_empty());
+//! # }
+//! ```
+//!
+//! The default elements are serialized in front of the container, so other serializers see a
+//! tuple instead of the container alone.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Name of the newtype struct wrapping a tuple of default elements followed by the container
+pub(crate) const TYPED_TOKEN: &str = "$serde_gvas::Typed";
+
+/// A container whose element types are known without any elements
+pub trait Container: Serialize {
+    /// Number of default elements, one for sequences and two (key and value) for maps
+    const PROTOTYPES: usize;
+
+    /// Serializes the default elements as elements of the given tuple
+    fn serialize_prototypes<S: SerializeTuple>(s: &mut S) -> Result<(), S::Error>;
+}
+
+/// Implements `Container` for a sequence of `T`
+macro_rules! seq_container {
+    ($($impl:tt)*) => {
+        $($impl)* {
+            const PROTOTYPES: usize = 1;
+
+            fn serialize_prototypes<S: SerializeTuple>(s: &mut S) -> Result<(), S::Error> {
+                s.serialize_element(&T::default())
+            }
+        }
+    }
+}
+
+seq_container!(impl<T: Serialize + Default> Container for [T]);
+seq_container!(impl<T: Serialize + Default> Container for Vec<T>);
+seq_container!(impl<T: Serialize + Default> Container for VecDeque<T>);
+seq_container!(impl<T: Serialize + Default + Ord> Container for BTreeSet<T>);
+seq_container!(impl<T: Serialize + Default + Eq + Hash, H: BuildHasher> Container for HashSet<T, H>);
+
+impl<K: Serialize + Default + Ord, V: Serialize + Default> Container for BTreeMap<K, V> {
+    const PROTOTYPES: usize = 2;
+
+    fn serialize_prototypes<S: SerializeTuple>(s: &mut S) -> Result<(), S::Error> {
+        s.serialize_element(&K::default())?;
+        s.serialize_element(&V::default())
+    }
+}
+
+impl<K: Serialize + Default + Eq + Hash, V: Serialize + Default, H: BuildHasher> Container for HashMap<K, V, H> {
+    const PROTOTYPES: usize = 2;
+
+    fn serialize_prototypes<S: SerializeTuple>(s: &mut S) -> Result<(), S::Error> {
+        s.serialize_element(&K::default())?;
+        s.serialize_element(&V::default())
+    }
+}
+
+/// The default elements followed by the container
+struct Typed<'a, C: Container + ?Sized + 'a>(&'a C);
+
+impl<'a, C: Container + ?Sized + 'a> Serialize for Typed<'a, C> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = s.serialize_tuple(C::PROTOTYPES + 1)?;
+        C::serialize_prototypes(&mut tuple)?;
+        tuple.serialize_element(self.0)?;
+        tuple.end()
+    }
+}
+
+/// Serialize the given container with its element types
+pub fn serialize<C: Container + ?Sized, S: Serializer>(value: &C, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_newtype_struct(TYPED_TOKEN, &Typed(value))
+}
+
+/// Deserialize a container, whose element types are part of the property anyway
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+    T::deserialize(d)
+}
+
+/// Serialize a sequence with its element type as SetProperty, see `serde_gvas::set`
+pub mod set {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use set::SET_TOKEN;
+    use super::Container;
+
+    /// The container wrapped by `serde_gvas::typed`
+    struct Typed<'a, C: Container + ?Sized + 'a>(&'a C);
+
+    impl<'a, C: Container + ?Sized + 'a> Serialize for Typed<'a, C> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            super::serialize(self.0, s)
+        }
+    }
+
+    /// Serialize the given sequence with its element type as SetProperty
+    pub fn serialize<C: Container + ?Sized, S: Serializer>(value: &C, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(SET_TOKEN, &Typed(value))
+    }
+
+    /// Deserialize a sequence from a SetProperty or ArrayProperty
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        T::deserialize(d)
+    }
+}
@@ -0,0 +1,764 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use serde::de::{self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+    VariantAccess, Visitor, value::{MapDeserializer, SeqDeserializer}};
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+
+use content::ContentSerializer;
+use delegate::{Delegate, MulticastDelegate, DELEGATE_TOKEN, MULTICAST_DELEGATE_TOKEN};
+use error::{Result, Error};
+use field_path::{FieldPath, FIELD_PATH_TOKEN};
+use guid::Guid;
+use soft_object::{SoftObjectPath, SOFT_OBJECT_PATH_TOKEN};
+use text::{FText, TEXT_TOKEN};
+
+/// Name of the enum `Value` is (de)serialized as
+///
+/// The GVAS Deserializer and Serializer recognize it and handle the type information
+/// contained in a `Value` instead of guessing it from the Rust type.
+pub(crate) const VALUE_TOKEN: &str = "$serde_gvas::Value";
+
+/// Type names of all variants of `Value`, which is also the name of the variant when (de)serialized
+const VARIANTS: &[&str] = &[
+    "BoolProperty",
+    "Int8Property",
+    "Int16Property",
+    "IntProperty",
+    "Int64Property",
+    "UInt16Property",
+    "UInt32Property",
+    "UInt64Property",
+    "QWordProperty",
+    "FloatProperty",
+    "DoubleProperty",
+    "StrProperty",
+    "TextProperty",
+    "NameProperty",
+    "ObjectProperty",
+    "SoftObjectProperty",
+    "SoftClassProperty",
+    "LazyObjectProperty",
+    "InterfaceProperty",
+    "DelegateProperty",
+    "MulticastInlineDelegateProperty",
+    "MulticastSparseDelegateProperty",
+    "FieldPathProperty",
+    "ByteProperty",
+    "EnumProperty",
+    "ArrayProperty",
+    "StructProperty",
+    "MapProperty",
+    "SetProperty",
+    "Unknown",
+];
+
+// Representation of a `Value` in the serde data model, e.g. in JSON:
+//
+// Value: {TypeName: Payload}
+// Payload of BoolProperty, IntProperty, ...: the primitive
+// Payload of TextProperty: FText, i.e. {flags, history}
+// Payload of SoftObjectProperty and SoftClassProperty: SoftObjectPath, i.e. {asset_path_name, sub_path_string}
+// Payload of LazyObjectProperty: Guid
+// Payload of InterfaceProperty: String
+// Payload of DelegateProperty: Delegate, i.e. {object, function_name}
+// Payload of MulticastInlineDelegateProperty and MulticastSparseDelegateProperty: [Delegate]
+// Payload of FieldPathProperty: FieldPath, i.e. {path, owner}
+// Payload of ByteProperty: u8 | (EnumName, Variant)
+// Payload of EnumProperty: (EnumName, Variant)
+// Payload of ArrayProperty: (InnerTypeName, Option<StructTypeName>, Option<StructGuid>, [Value])
+// Payload of StructProperty: (StructTypeName, StructGuid, [Property])
+// Payload of MapProperty: (KeyTypeName, ValueTypeName, [RemovedKey], [(Key, Value)])
+// Payload of SetProperty: (InnerTypeName, [RemovedElement], [Value])
+// Payload of Unknown: (TypeName, Option<InnerTypeName>, Option<ValueTypeName>, [u8])
+// Property: {name, [array_index], [guid], value}
+
+/// Any value of a GVAS file
+///
+/// In contrast to deserializing into a Rust type, a `Value` can be used to read and write files
+/// without knowing their layout beforehand. All type information needed to write the value back
+/// is preserved, e.g. the type name of each property and the struct type name of structs. Only the
+/// encoding of strings isn't, they are written as ASCII if possible and as UTF-16 otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int8(i8),
+    Int16(i16),
+    Int(i32),
+    Int64(i64),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    /// QWordProperty, which older games use for signed 64 bit integers instead of Int64Property
+    QWord(i64),
+    Float(f32),
+    Double(f64),
+    Str(String),
+    Text(FText),
+    Name(String),
+    Object(String),
+    /// SoftObjectProperty, i.e. the path of an asset which is loaded on demand
+    SoftObject(SoftObjectPath),
+    /// SoftClassProperty, i.e. the path of a class which is loaded on demand
+    SoftClass(SoftObjectPath),
+    /// LazyObjectProperty, i.e. the GUID identifying an object
+    LazyObject(Guid),
+    /// InterfaceProperty, i.e. the path of the object implementing the interface
+    Interface(String),
+    Delegate(Delegate),
+    MulticastInlineDelegate(MulticastDelegate),
+    MulticastSparseDelegate(MulticastDelegate),
+    FieldPath(FieldPath),
+    /// ByteProperty holding a raw byte, whose enum name is `None`
+    UInt8(u8),
+    /// ByteProperty holding an enum variant
+    Byte { enum_name: String, variant: String },
+    /// EnumProperty holding an enum variant, which is usually prefixed with the enum name
+    /// (e.g. `EFoo::Bar`)
+    Enum { enum_name: String, variant: String },
+    /// ArrayProperty with the type name of its elements
+    ///
+    /// Arrays of structs contain the struct type of their elements from version 500 onwards.
+    Array { inner_type: String, struct_type: Option<StructType>, elements: Vec<Value> },
+    /// StructProperty with its struct type name and GUID
+    ///
+    /// The fields of native structs like `LinearColor` are represented as properties as well.
+    Struct { type_name: String, guid: Guid, properties: Vec<Property> },
+    /// MapProperty with the type names of its keys and values
+    ///
+    /// Keys and values that are structs don't have a struct type name. Removed keys are the keys
+    /// of the object's archetype that aren't present anymore, which is usually empty in saves.
+    Map { key_type: String, value_type: String, removed: Vec<Value>, entries: Vec<(Value, Value)> },
+    /// SetProperty with the type name of its elements
+    ///
+    /// Like the keys of maps, elements that are structs don't have a struct type name.
+    Set { inner_type: String, removed: Vec<Value>, elements: Vec<Value> },
+    /// Property of a type this crate doesn't know, or whose elements, keys or values are of such a type
+    ///
+    /// The type names are those of its tag, and its body is kept as it is and written back unchanged.
+    Unknown { type_name: String, inner_type: Option<String>, value_type: Option<String>, bytes: Vec<u8> },
+}
+
+/// Struct type of the elements of an array of structs
+///
+/// It's written in front of the elements even if the array is empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructType {
+    pub name: String,
+    pub guid: Guid,
+}
+
+/// A named value inside a struct
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    /// Index of the element of a static array (e.g. `int32 Foo[4]`), whose elements are
+    /// stored as separate properties with the same name
+    pub array_index: u32,
+    /// Optional GUID of the property
+    pub guid: Option<Guid>,
+    pub value: Value,
+}
+
+impl Property {
+    /// Create a property with array index 0 and without GUID
+    pub fn new<S: Into<String>>(name: S, value: Value) -> Property {
+        Property {
+            name: name.into(),
+            array_index: 0,
+            guid: None,
+            value,
+        }
+    }
+}
+
+/// Deserialize an instance of `T` from a `Value`
+pub fn from_value<T: de::DeserializeOwned>(value: Value) -> Result<T> {
+    T::deserialize(value)
+}
+
+impl Value {
+    /// Name of the property type of this value as used in GVAS files
+    pub fn type_name(&self) -> &str {
+        match self {
+            Value::Unknown { type_name, .. } => type_name,
+            _ => VARIANTS[self.variant_index() as usize],
+        }
+    }
+
+    fn variant_index(&self) -> u32 {
+        match self {
+            Value::Bool(_) => 0,
+            Value::Int8(_) => 1,
+            Value::Int16(_) => 2,
+            Value::Int(_) => 3,
+            Value::Int64(_) => 4,
+            Value::UInt16(_) => 5,
+            Value::UInt32(_) => 6,
+            Value::UInt64(_) => 7,
+            Value::QWord(_) => 8,
+            Value::Float(_) => 9,
+            Value::Double(_) => 10,
+            Value::Str(_) => 11,
+            Value::Text(_) => 12,
+            Value::Name(_) => 13,
+            Value::Object(_) => 14,
+            Value::SoftObject(_) => 15,
+            Value::SoftClass(_) => 16,
+            Value::LazyObject(_) => 17,
+            Value::Interface(_) => 18,
+            Value::Delegate(_) => 19,
+            Value::MulticastInlineDelegate(_) => 20,
+            Value::MulticastSparseDelegate(_) => 21,
+            Value::FieldPath(_) => 22,
+            Value::UInt8(_) | Value::Byte { .. } => 23,
+            Value::Enum { .. } => 24,
+            Value::Array { .. } => 25,
+            Value::Struct { .. } => 26,
+            Value::Map { .. } => 27,
+            Value::Set { .. } => 28,
+            Value::Unknown { .. } => 29,
+        }
+    }
+
+    /// Returns the value of the first property with the given name if this is a struct
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct { properties, .. } => properties.iter()
+                .find(|property| property.name == name)
+                .map(|property| &property.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first property with the given name if this is a struct
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match self {
+            Value::Struct { properties, .. } => properties.iter_mut()
+                .find(|property| property.name == name)
+                .map(|property| &mut property.value),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Index<&'a str> for Value {
+    type Output = Value;
+
+    /// Returns the value of the first property with the given name
+    ///
+    /// Panics if this isn't a struct or it doesn't contain the property.
+    fn index(&self, name: &'a str) -> &Value {
+        match self.get(name) {
+            Some(value) => value,
+            None => panic!("no property `{}` in {}", name, self.type_name()),
+        }
+    }
+}
+
+impl<'a> IndexMut<&'a str> for Value {
+    fn index_mut(&mut self, name: &'a str) -> &mut Value {
+        if self.get(name).is_none() {
+            panic!("no property `{}` in {}", name, self.type_name());
+        }
+        self.get_mut(name).unwrap()
+    }
+}
+
+impl Index<usize> for Value {
+    type Output = Value;
+
+    /// Returns the array or set element at the given position
+    ///
+    /// Panics if this isn't an array or set or the index is out of bounds.
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Array { elements, .. } | Value::Set { elements, .. } => &elements[index],
+            _ => panic!("cannot index into {} with {}", self.type_name(), index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Value {
+    fn index_mut(&mut self, index: usize) -> &mut Value {
+        match self {
+            Value::Array { elements, .. } | Value::Set { elements, .. } => &mut elements[index],
+            _ => panic!("cannot index into {} with {}", self.type_name(), index),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, s: S) -> ::std::result::Result<S::Ok, S::Error> {
+        let index = self.variant_index();
+        let name = VARIANTS[index as usize];
+        match self {
+            Value::Bool(b) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, b),
+            Value::Int8(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::Int16(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::Int(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::Int64(i) | Value::QWord(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::UInt16(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::UInt32(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::UInt64(i) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, i),
+            Value::Float(f) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, f),
+            Value::Double(f) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, f),
+            Value::Str(st) | Value::Name(st) | Value::Object(st) | Value::Interface(st) =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, st),
+            Value::Text(text) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, text),
+            Value::SoftObject(path) | Value::SoftClass(path) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, path),
+            Value::LazyObject(guid) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, guid),
+            Value::Delegate(delegate) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, delegate),
+            Value::MulticastInlineDelegate(delegates) | Value::MulticastSparseDelegate(delegates) =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, delegates),
+            Value::FieldPath(path) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, path),
+            Value::UInt8(b) => s.serialize_newtype_variant(VALUE_TOKEN, index, name, b),
+            Value::Byte { enum_name, variant } | Value::Enum { enum_name, variant } =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(enum_name, variant)),
+            Value::Array { inner_type, struct_type, elements } => {
+                let struct_name = struct_type.as_ref().map(|struct_type| &struct_type.name);
+                let struct_guid = struct_type.as_ref().map(|struct_type| struct_type.guid);
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(inner_type, struct_name, struct_guid, elements))
+            }
+            Value::Struct { type_name, guid, properties } =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(type_name, guid, properties)),
+            Value::Map { key_type, value_type, removed, entries } =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(key_type, value_type, removed, entries)),
+            Value::Set { inner_type, removed, elements } =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(inner_type, removed, elements)),
+            Value::Unknown { type_name, inner_type, value_type, bytes } =>
+                s.serialize_newtype_variant(VALUE_TOKEN, index, name, &(type_name, inner_type, value_type, bytes)),
+        }
+    }
+}
+
+impl Serialize for Property {
+    fn serialize<S: Serializer>(&self, s: S) -> ::std::result::Result<S::Ok, S::Error> {
+        let len = 2 + (self.array_index != 0) as usize + self.guid.is_some() as usize;
+        let mut property = s.serialize_struct("Property", len)?;
+        property.serialize_field("name", &self.name)?;
+        if self.array_index != 0 {
+            property.serialize_field("array_index", &self.array_index)?;
+        }
+        if let Some(ref guid) = self.guid {
+            property.serialize_field("guid", guid)?;
+        }
+        property.serialize_field("value", &self.value)?;
+        property.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> ::std::result::Result<Value, D::Error> {
+        d.deserialize_enum(VALUE_TOKEN, VARIANTS, ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GVAS value tagged with its property type")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> ::std::result::Result<Value, A::Error> {
+        let (typ, variant): (String, _) = data.variant()?;
+        Ok(match typ.as_str() {
+            "BoolProperty" => Value::Bool(variant.newtype_variant()?),
+            "Int8Property" => Value::Int8(variant.newtype_variant()?),
+            "Int16Property" => Value::Int16(variant.newtype_variant()?),
+            "IntProperty" => Value::Int(variant.newtype_variant()?),
+            "Int64Property" => Value::Int64(variant.newtype_variant()?),
+            "UInt16Property" => Value::UInt16(variant.newtype_variant()?),
+            "UInt32Property" => Value::UInt32(variant.newtype_variant()?),
+            "UInt64Property" => Value::UInt64(variant.newtype_variant()?),
+            "QWordProperty" => Value::QWord(variant.newtype_variant()?),
+            "FloatProperty" => Value::Float(variant.newtype_variant()?),
+            "DoubleProperty" => Value::Double(variant.newtype_variant()?),
+            "StrProperty" => Value::Str(variant.newtype_variant()?),
+            "TextProperty" => Value::Text(variant.newtype_variant()?),
+            "NameProperty" => Value::Name(variant.newtype_variant()?),
+            "ObjectProperty" => Value::Object(variant.newtype_variant()?),
+            "SoftObjectProperty" => Value::SoftObject(variant.newtype_variant()?),
+            "SoftClassProperty" => Value::SoftClass(variant.newtype_variant()?),
+            "LazyObjectProperty" => Value::LazyObject(variant.newtype_variant()?),
+            "InterfaceProperty" => Value::Interface(variant.newtype_variant()?),
+            "DelegateProperty" => Value::Delegate(variant.newtype_variant()?),
+            "MulticastInlineDelegateProperty" => Value::MulticastInlineDelegate(variant.newtype_variant()?),
+            "MulticastSparseDelegateProperty" => Value::MulticastSparseDelegate(variant.newtype_variant()?),
+            "FieldPathProperty" => Value::FieldPath(variant.newtype_variant()?),
+            "ByteProperty" => match variant.newtype_variant()? {
+                BytePayload::Raw(b) => Value::UInt8(b),
+                BytePayload::Enum(enum_name, variant) => Value::Byte { enum_name, variant },
+            },
+            "EnumProperty" => {
+                let (enum_name, variant) = variant.newtype_variant()?;
+                Value::Enum { enum_name, variant }
+            }
+            "ArrayProperty" => {
+                let (inner_type, struct_name, struct_guid, elements): (_, Option<String>, Option<Guid>, _) = variant.newtype_variant()?;
+                let struct_type = struct_name.map(|name| StructType { name, guid: struct_guid.unwrap_or_default() });
+                Value::Array { inner_type, struct_type, elements }
+            }
+            "StructProperty" => {
+                let (type_name, guid, properties) = variant.newtype_variant()?;
+                Value::Struct { type_name, guid, properties }
+            }
+            "MapProperty" => {
+                let (key_type, value_type, removed, entries) = variant.newtype_variant()?;
+                Value::Map { key_type, value_type, removed, entries }
+            }
+            "SetProperty" => {
+                let (inner_type, removed, elements) = variant.newtype_variant()?;
+                Value::Set { inner_type, removed, elements }
+            }
+            "Unknown" => {
+                let (type_name, inner_type, value_type, bytes) = variant.newtype_variant()?;
+                Value::Unknown { type_name, inner_type, value_type, bytes }
+            }
+            typ => return Err(de::Error::unknown_variant(typ, VARIANTS)),
+        })
+    }
+}
+
+const PROPERTY_FIELDS: &[&str] = &["name", "array_index", "guid", "value"];
+
+impl<'de> Deserialize<'de> for Property {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> ::std::result::Result<Property, D::Error> {
+        d.deserialize_struct("Property", PROPERTY_FIELDS, PropertyVisitor)
+    }
+}
+
+struct PropertyVisitor;
+
+impl<'de> Visitor<'de> for PropertyVisitor {
+    type Value = Property;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a property")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> ::std::result::Result<Property, A::Error> {
+        let mut name = None;
+        let mut array_index = 0;
+        let mut guid = None;
+        let mut value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "name" => name = Some(map.next_value()?),
+                "array_index" => array_index = map.next_value()?,
+                "guid" => guid = map.next_value()?,
+                "value" => value = Some(map.next_value()?),
+                key => return Err(de::Error::unknown_field(key, PROPERTY_FIELDS)),
+            }
+        }
+        Ok(Property {
+            name: name.ok_or_else(|| de::Error::missing_field("name"))?,
+            array_index,
+            guid,
+            value: value.ok_or_else(|| de::Error::missing_field("value"))?,
+        })
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+/// A `Value` can be deserialized either as the type it holds (e.g. into a Rust struct) or,
+/// if the `Value` itself is deserialized, as enum tagged with its type name.
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        match self {
+            Value::Bool(b) => v.visit_bool(b),
+            Value::Int8(i) => v.visit_i8(i),
+            Value::Int16(i) => v.visit_i16(i),
+            Value::Int(i) => v.visit_i32(i),
+            Value::Int64(i) | Value::QWord(i) => v.visit_i64(i),
+            Value::UInt16(i) => v.visit_u16(i),
+            Value::UInt32(i) => v.visit_u32(i),
+            Value::UInt64(i) => v.visit_u64(i),
+            Value::Float(f) => v.visit_f32(f),
+            Value::Double(f) => v.visit_f64(f),
+            Value::Str(s) | Value::Name(s) | Value::Object(s) | Value::Interface(s) => v.visit_string(s),
+            Value::Text(text) => v.visit_string(text.to_string()),
+            Value::SoftObject(path) | Value::SoftClass(path) => v.visit_string(path.to_string()),
+            Value::LazyObject(guid) => v.visit_string(guid.to_string()),
+            Value::Delegate(delegate) => v.visit_string(delegate.to_string()),
+            Value::MulticastInlineDelegate(delegates) | Value::MulticastSparseDelegate(delegates) =>
+                v.visit_seq(SeqDeserializer::new(delegates.0.into_iter().map(|delegate| delegate.to_string()))),
+            Value::FieldPath(path) => v.visit_string(path.to_string()),
+            Value::UInt8(b) => v.visit_u8(b),
+            Value::Byte { variant, .. } | Value::Enum { variant, .. } => {
+                // Rust enums only know the variant, not the enum name it's usually prefixed with
+                let variant = match variant.rfind("::") {
+                    Some(i) => variant[i + 2..].to_string(),
+                    None => variant,
+                };
+                v.visit_enum(variant.into_deserializer())
+            }
+            Value::Array { elements, .. } | Value::Set { elements, .. } =>
+                v.visit_seq(SeqDeserializer::new(elements.into_iter())),
+            Value::Struct { type_name, mut properties, .. } => {
+                // a GameplayTagContainer only consists of its tags, so it's visited as sequence of them
+                if type_name == "GameplayTagContainer" && properties.len() == 1 && properties[0].name == "GameplayTags" {
+                    return properties.remove(0).value.deserialize_any(v);
+                }
+                v.visit_map(MapDeserializer::new(properties.into_iter().map(|p| (p.name, p.value))))
+            }
+            Value::Map { entries, .. } => v.visit_map(MapDeserializer::new(entries.into_iter())),
+            // like the Deserializer, which skips properties of unknown types
+            Value::Unknown { .. } => v.visit_unit(),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, _variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        match self {
+            value if name == VALUE_TOKEN => v.visit_enum(TypedAccess(value)),
+            Value::Str(s) | Value::Name(s) => v.visit_enum(s.into_deserializer()),
+            value => value.deserialize_any(v),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        v.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, v: V) -> Result<V::Value> {
+        match self {
+            // types like `FText` are deserialized from all of their fields instead of their string
+            Value::Text(text) if name == TEXT_TOKEN => v.visit_newtype_struct(text.serialize(ContentSerializer)?),
+            Value::SoftObject(path) | Value::SoftClass(path) if name == SOFT_OBJECT_PATH_TOKEN =>
+                v.visit_newtype_struct(path.serialize(ContentSerializer)?),
+            Value::Delegate(delegate) if name == DELEGATE_TOKEN => v.visit_newtype_struct(delegate.serialize(ContentSerializer)?),
+            Value::MulticastInlineDelegate(delegates) | Value::MulticastSparseDelegate(delegates) if name == MULTICAST_DELEGATE_TOKEN =>
+                v.visit_newtype_struct(delegates.serialize(ContentSerializer)?),
+            Value::FieldPath(path) if name == FIELD_PATH_TOKEN => v.visit_newtype_struct(path.serialize(ContentSerializer)?),
+            value => v.visit_newtype_struct(value),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        // the value is already parsed, and `IgnoredAny` can't visit enums
+        v.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+/// Payload of a ByteProperty, which is either a raw byte or `(EnumName, Variant)`
+enum BytePayload {
+    Raw(u8),
+    Enum(String, String),
+}
+
+impl<'de> Deserialize<'de> for BytePayload {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> ::std::result::Result<BytePayload, D::Error> {
+        d.deserialize_any(BytePayloadVisitor)
+    }
+}
+
+struct BytePayloadVisitor;
+
+impl<'de> Visitor<'de> for BytePayloadVisitor {
+    type Value = BytePayload;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a byte or a tuple of enum name and variant")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> ::std::result::Result<BytePayload, E> {
+        if v > u64::from(u8::MAX) {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(v), &self));
+        }
+        Ok(BytePayload::Raw(v as u8))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> ::std::result::Result<BytePayload, E> {
+        if !(0..=i64::from(u8::MAX)).contains(&v) {
+            return Err(E::invalid_value(de::Unexpected::Signed(v), &self));
+        }
+        Ok(BytePayload::Raw(v as u8))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> ::std::result::Result<BytePayload, A::Error> {
+        let enum_name = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let variant = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(BytePayload::Enum(enum_name, variant))
+    }
+}
+
+/// Accesses a `Value` as enum with its type name as variant
+struct TypedAccess(Value);
+
+impl<'de> EnumAccess<'de> for TypedAccess {
+    type Error = Error;
+    type Variant = TypedAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, TypedAccess)> {
+        let type_name: de::value::StrDeserializer<Error> = VARIANTS[self.0.variant_index() as usize].into_deserializer();
+        let typ = seed.deserialize(type_name)?;
+        Ok((typ, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TypedAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Err(de::Error::invalid_type(de::Unexpected::NewtypeVariant, &"unit variant"))
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        match self.0 {
+            Value::Byte { enum_name, variant } | Value::Enum { enum_name, variant } =>
+                seed.deserialize(SeqDeserializer::new(vec![enum_name, variant].into_iter())),
+            Value::Array { inner_type, struct_type, elements } => {
+                let (struct_name, struct_guid) = match struct_type {
+                    Some(struct_type) => (Some(struct_type.name), Some(struct_type.guid.to_string())),
+                    None => (None, None),
+                };
+                seed.deserialize(SeqDeserializer::new(vec![
+                    Payload::Str(inner_type),
+                    Payload::OptionStr(struct_name),
+                    Payload::OptionStr(struct_guid),
+                    Payload::Elements(elements),
+                ].into_iter()))
+            }
+            Value::Struct { type_name, guid, properties } =>
+                seed.deserialize(SeqDeserializer::new(vec![
+                    Payload::Str(type_name),
+                    Payload::Str(guid.to_string()),
+                    Payload::Properties(properties),
+                ].into_iter())),
+            Value::Map { key_type, value_type, removed, entries } =>
+                seed.deserialize(SeqDeserializer::new(vec![
+                    Payload::Str(key_type),
+                    Payload::Str(value_type),
+                    Payload::Elements(removed),
+                    Payload::Entries(entries),
+                ].into_iter())),
+            Value::Set { inner_type, removed, elements } =>
+                seed.deserialize(SeqDeserializer::new(vec![
+                    Payload::Str(inner_type),
+                    Payload::Elements(removed),
+                    Payload::Elements(elements),
+                ].into_iter())),
+            Value::Unknown { type_name, inner_type, value_type, bytes } =>
+                seed.deserialize(SeqDeserializer::new(vec![
+                    Payload::Str(type_name),
+                    Payload::OptionStr(inner_type),
+                    Payload::OptionStr(value_type),
+                    Payload::Bytes(bytes),
+                ].into_iter())),
+            value => seed.deserialize(value),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _v: V) -> Result<V::Value> {
+        Err(de::Error::invalid_type(de::Unexpected::NewtypeVariant, &"tuple variant"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], _v: V) -> Result<V::Value> {
+        Err(de::Error::invalid_type(de::Unexpected::NewtypeVariant, &"struct variant"))
+    }
+}
+
+/// Parts of the payload of compound values
+enum Payload {
+    Str(String),
+    OptionStr(Option<String>),
+    U32(u32),
+    Value(Value),
+    Elements(Vec<Value>),
+    Properties(Vec<Property>),
+    Entries(Vec<(Value, Value)>),
+    Entry(Value, Value),
+    Bytes(Vec<u8>),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Payload {
+    type Deserializer = Payload;
+
+    fn into_deserializer(self) -> Payload {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Payload {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        match self {
+            Payload::Str(s) | Payload::OptionStr(Some(s)) => v.visit_string(s),
+            Payload::OptionStr(None) => v.visit_none(),
+            Payload::U32(u) => v.visit_u32(u),
+            Payload::Value(value) => de::Deserializer::deserialize_any(value, v),
+            Payload::Elements(elements) => v.visit_seq(SeqDeserializer::new(elements.into_iter())),
+            Payload::Properties(properties) => v.visit_seq(SeqDeserializer::new(properties.into_iter())),
+            Payload::Entries(entries) =>
+                v.visit_seq(SeqDeserializer::new(entries.into_iter().map(|(key, value)| Payload::Entry(key, value)))),
+            Payload::Entry(key, value) => v.visit_seq(SeqDeserializer::new(vec![key, value].into_iter())),
+            Payload::Bytes(bytes) => v.visit_seq(SeqDeserializer::new(bytes.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        match self {
+            Payload::OptionStr(None) => v.visit_none(),
+            payload => v.visit_some(payload),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], v: V) -> Result<V::Value> {
+        match self {
+            Payload::Value(value) => de::Deserializer::deserialize_enum(value, name, variants, v),
+            payload => payload.deserialize_any(v),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Property {
+    type Deserializer = Property;
+
+    fn into_deserializer(self) -> Property {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Property {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
+        v.visit_map(MapDeserializer::new(vec![
+            ("name", Payload::Str(self.name)),
+            ("array_index", Payload::U32(self.array_index)),
+            ("guid", Payload::OptionStr(self.guid.map(|guid| guid.to_string()))),
+            ("value", Payload::Value(self.value)),
+        ].into_iter()))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
@@ -0,0 +1,97 @@
+extern crate serde;
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde_gvas::{Guid, Property, Value, from_slice, to_vec};
+
+use common::{header, properties, property, round_trip, string};
+
+/// Bytes which are (de)serialized at once instead of one by one
+#[derive(Debug, PartialEq)]
+struct Blob(Vec<u8>);
+
+impl serde::Serialize for Blob {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Blob {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Blob, D::Error> {
+        struct BlobVisitor;
+
+        impl<'de> Visitor<'de> for BlobVisitor {
+            type Value = Blob;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Blob, E> {
+                Ok(Blob(v))
+            }
+        }
+
+        d.deserialize_byte_buf(BlobVisitor)
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    flag: u8,
+    list: Vec<u8>,
+    blob: Blob,
+}
+
+#[test]
+fn raw_bytes() {
+    let value = Save { flag: 0xfe, list: vec![1, 2], blob: Blob(vec![3, 4, 5]) };
+    let bytes = to_vec(&header(), &value).unwrap();
+    // raw bytes have the enum name `None`, and arrays of them consist of the bytes only
+    let expected = [
+        property("flag", "ByteProperty", &string("None"), &[0xfe]),
+        property("list", "ArrayProperty", &string("ByteProperty"), &[2, 0, 0, 0, 1, 2]),
+        property("blob", "ArrayProperty", &string("ByteProperty"), &[3, 0, 0, 0, 3, 4, 5]),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), value);
+    let parsed = round_trip(&bytes);
+    assert_eq!(parsed["flag"], Value::UInt8(0xfe));
+    assert_eq!(parsed["blob"][2], Value::UInt8(5));
+}
+
+#[test]
+fn enum_variants() {
+    let byte = Value::Byte { enum_name: "EColor".to_string(), variant: "EColor::Red".to_string() };
+    let array = Value::Array {
+        inner_type: "ByteProperty".to_string(),
+        struct_type: None,
+        elements: vec![
+            Value::Byte { enum_name: "EColor".to_string(), variant: "EColor::Green".to_string() },
+            Value::Byte { enum_name: "EColor".to_string(), variant: "EColor::Blue".to_string() },
+        ],
+    };
+    let save = Value::Struct {
+        type_name: String::new(),
+        guid: Guid::default(),
+        properties: vec![Property::new("Color", byte.clone()), Property::new("Colors", array.clone())],
+    };
+    let bytes = to_vec(&header(), &save).unwrap();
+    // arrays of variants are told apart from raw bytes by their length
+    let variants = [&2u32.to_le_bytes()[..], &string("EColor::Green"), &string("EColor::Blue")].concat();
+    let expected = [
+        property("Color", "ByteProperty", &string("EColor"), &string("EColor::Red")),
+        property("Colors", "ArrayProperty", &string("ByteProperty"), &variants),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    let parsed = round_trip(&bytes);
+    assert_eq!(parsed["Color"], byte);
+    assert_eq!(parsed["Colors"], array);
+}
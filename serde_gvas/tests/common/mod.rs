@@ -0,0 +1,97 @@
+//! Helpers shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_gvas::{DeserializerOptions, EngineVersion, GvasHeader, Value, from_reader_with_options, to_vec};
+
+/// Returns the bytes of one of the sample files
+pub fn sample(name: &str) -> Vec<u8> {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "files", name].iter().collect();
+    fs::read(path).unwrap()
+}
+
+/// Header of a file saved with UE 4.27, which has property GUIDs and tags in front of arrays of structs
+pub fn header() -> GvasHeader {
+    GvasHeader {
+        save_game_version: 2,
+        package_version: 522,
+        package_version_ue5: 0,
+        engine_version: EngineVersion { major: 4, minor: 27, patch: 2, changelist: 0, branch: "++UE4+Release-4.27".to_string() },
+        custom_version_format: 3,
+        custom_versions: Vec::new(),
+        save_game_class_name: "TestSave".to_string(),
+        trailing_bytes: Vec::new(),
+    }
+}
+
+/// Header of a file saved with UE 5.3, which has large world coordinates and soft object paths without asset path names
+pub fn header_ue5() -> GvasHeader {
+    GvasHeader {
+        save_game_version: 3,
+        package_version_ue5: 1009,
+        engine_version: EngineVersion { major: 5, minor: 3, patch: 2, changelist: 0, branch: "++UE5+Release-5.3".to_string() },
+        ..header()
+    }
+}
+
+/// Parses the bytes as `Value` and asserts that writing it reproduces them
+pub fn round_trip(bytes: &[u8]) -> Value {
+    round_trip_with_options(bytes, &DeserializerOptions::default())
+}
+
+/// Parses the bytes as `Value` with the given options and asserts that writing it reproduces them
+pub fn round_trip_with_options(bytes: &[u8], options: &DeserializerOptions) -> Value {
+    let (header, value): (GvasHeader, Value) = from_reader_with_options(bytes, options).unwrap();
+    assert_eq!(to_vec(&header, &value).unwrap(), bytes);
+    value
+}
+
+/// Returns the properties of a file written with the given header, without their terminating `None`
+pub fn properties<'a>(header: &GvasHeader, bytes: &'a [u8]) -> &'a [u8] {
+    let mut written = Vec::new();
+    header.write(&mut written).unwrap();
+    assert_eq!(&bytes[..written.len()], &written[..]);
+    // `None` and the trailing bytes
+    &bytes[written.len()..bytes.len() - 9 - 4]
+}
+
+/// Returns the offset of the `nth` occurrence of the string `s` including its length and terminator
+pub fn find_string(bytes: &[u8], s: &str, nth: usize) -> usize {
+    let mut needle = (s.len() as u32 + 1).to_le_bytes().to_vec();
+    needle.extend_from_slice(s.as_bytes());
+    needle.push(0);
+    bytes.windows(needle.len()).enumerate()
+        .filter(|&(_, window)| window == &needle[..])
+        .nth(nth)
+        .map(|(offset, _)| offset)
+        .unwrap()
+}
+
+/// Encodes an ASCII string with its length and terminator, or as length 0 if it is empty
+pub fn string(s: &str) -> Vec<u8> {
+    if s.is_empty() {
+        return vec![0; 4];
+    }
+    let mut bytes = (s.len() as u32 + 1).to_le_bytes().to_vec();
+    bytes.extend_from_slice(s.as_bytes());
+    bytes.push(0);
+    bytes
+}
+
+/// Encodes a property of a file written with `header()`
+///
+/// The tag data is everything between the array index and the property GUID, e.g. the struct name.
+pub fn property(name: &str, typ: &str, tag_data: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut bytes = string(name);
+    bytes.extend(string(typ));
+    bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(tag_data);
+    // no property GUID
+    bytes.push(0);
+    bytes.extend_from_slice(body);
+    bytes
+}
@@ -0,0 +1,75 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde_gvas::{DateTime, ErrorKind, Timespan, from_slice, to_vec};
+
+use common::{header, properties, property, string};
+
+/// Ticks of 1970-01-01
+const UNIX_EPOCH_TICKS: i64 = 621_355_968_000_000_000;
+
+fn assert_round_trip(date_time: DateTime, s: &str) {
+    assert_eq!(date_time.to_string(), s);
+    assert_eq!(s.parse::<DateTime>().unwrap(), date_time);
+}
+
+#[test]
+fn epoch() {
+    assert_round_trip(DateTime { ticks: 0 }, "0001-01-01T00:00:00Z");
+    assert_round_trip(DateTime { ticks: UNIX_EPOCH_TICKS }, "1970-01-01T00:00:00Z");
+    assert_eq!(DateTime { ticks: UNIX_EPOCH_TICKS }.to_system_time(), Some(UNIX_EPOCH));
+    assert_eq!(DateTime::from(UNIX_EPOCH), DateTime { ticks: UNIX_EPOCH_TICKS });
+    assert_round_trip(DateTime { ticks: UNIX_EPOCH_TICKS + 15_205_437_022_500_000 }, "2018-03-08T21:15:02.25Z");
+}
+
+#[test]
+fn pre_epoch() {
+    let before_unix_epoch = DateTime { ticks: UNIX_EPOCH_TICKS - 10_000_000 };
+    assert_round_trip(before_unix_epoch, "1969-12-31T23:59:59Z");
+    assert_eq!(before_unix_epoch.to_system_time(), UNIX_EPOCH.checked_sub(Duration::from_secs(1)));
+    // the year before 1 is 0, the one before that -1
+    assert_round_trip(DateTime { ticks: -1 }, "0000-12-31T23:59:59.9999999Z");
+    assert_round_trip(DateTime { ticks: -367 * 24 * 36_000_000_000 }, "-0001-12-31T00:00:00Z");
+}
+
+#[test]
+fn extreme_values() {
+    assert_round_trip(DateTime { ticks: i64::MAX }, "29228-09-14T02:48:05.4775807Z");
+    assert_round_trip(DateTime { ticks: i64::MIN }, "-29227-04-19T21:11:54.5224192Z");
+    // neither may overflow, whether or not the platform can represent them
+    DateTime { ticks: i64::MAX }.to_system_time();
+    DateTime { ticks: i64::MIN }.to_system_time();
+    assert_round_trip("10000-01-01T00:00:00Z".parse().unwrap(), "10000-01-01T00:00:00Z");
+    for s in &["29228-09-14T02:48:05.4775808Z", "-29227-04-19T21:11:54.5224191Z", "99999999999999999-01-01T00:00:00Z", "999-01-01T00:00:00Z"] {
+        let e = s.parse::<DateTime>().unwrap_err();
+        assert!(matches!(e.kind(), ErrorKind::InvalidDateTime(_)), "{}", s);
+    }
+    for &ticks in &[i64::MIN, i64::MAX] {
+        let timespan = Timespan { ticks };
+        assert_eq!(timespan.to_string().parse::<Timespan>().unwrap(), timespan);
+    }
+    assert!("P10675200D".parse::<Timespan>().is_err());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Times {
+    date_time: DateTime,
+    timespan: Timespan,
+}
+
+#[test]
+fn ticks_are_written_as_they_are() {
+    for &ticks in &[0, -1, i64::MIN, i64::MAX] {
+        let value = Times { date_time: DateTime { ticks }, timespan: Timespan { ticks } };
+        let bytes = to_vec(&header(), &value).unwrap();
+        let mut expected = property("date_time", "StructProperty", &[&string("DateTime")[..], &[0; 16]].concat(), &ticks.to_le_bytes());
+        expected.extend(property("timespan", "StructProperty", &[&string("Timespan")[..], &[0; 16]].concat(), &ticks.to_le_bytes()));
+        assert_eq!(properties(&header(), &bytes), &expected[..]);
+        assert_eq!(from_slice::<Times>(&bytes).unwrap(), value);
+    }
+}
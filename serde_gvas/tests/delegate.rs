@@ -0,0 +1,77 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{Delegate, FieldPath, Guid, MulticastDelegate, Property, Value, from_slice, to_vec};
+
+use common::{header, properties, property, round_trip, string};
+
+fn delegate(function_name: &str) -> Delegate {
+    Delegate { object: "/Game/Map.Map:PersistentLevel.Door".to_string(), function_name: function_name.to_string() }
+}
+
+fn field_path() -> FieldPath {
+    FieldPath { path: vec!["bHidden".to_string(), "/Script/Engine.Actor".to_string()], owner: "/Script/Engine.Actor".to_string() }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    on_open: Delegate,
+    on_close: MulticastDelegate,
+    field: FieldPath,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Strings {
+    on_open: String,
+    on_close: Vec<String>,
+    field: String,
+}
+
+fn save() -> Save {
+    Save { on_open: delegate("Open"), on_close: MulticastDelegate(vec![delegate("Close"), delegate("Lock")]), field: field_path() }
+}
+
+#[test]
+fn wire_format() {
+    let bytes = to_vec(&header(), &save()).unwrap();
+    let door = string("/Game/Map.Map:PersistentLevel.Door");
+    let on_close = [&2u32.to_le_bytes()[..], &door, &string("Close"), &door, &string("Lock")].concat();
+    let field = [&2u32.to_le_bytes()[..], &string("bHidden"), &string("/Script/Engine.Actor"), &string("/Script/Engine.Actor")].concat();
+    let expected = [
+        property("on_open", "DelegateProperty", &[], &[&door[..], &string("Open")].concat()),
+        property("on_close", "MulticastInlineDelegateProperty", &[], &on_close),
+        property("field", "FieldPathProperty", &[], &field),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), save());
+    let strings = from_slice::<Strings>(&bytes).unwrap();
+    assert_eq!(strings.on_open, "/Game/Map.Map:PersistentLevel.Door.Open");
+    assert_eq!(strings.on_close[1], "/Game/Map.Map:PersistentLevel.Door.Lock");
+    assert_eq!(strings.field, "/Script/Engine.Actor:bHidden");
+    round_trip(&bytes);
+}
+
+#[test]
+fn sparse_delegate_and_interface() {
+    let save = Value::Struct {
+        type_name: String::new(),
+        guid: Guid::default(),
+        properties: vec![
+            Property::new("OnHit", Value::MulticastSparseDelegate(MulticastDelegate(vec![delegate("Hit")]))),
+            Property::new("Target", Value::Interface("/Game/Map.Map:PersistentLevel.Door".to_string())),
+        ],
+    };
+    let bytes = to_vec(&header(), &save).unwrap();
+    let door = string("/Game/Map.Map:PersistentLevel.Door");
+    let expected = [
+        property("OnHit", "MulticastSparseDelegateProperty", &[], &[&1u32.to_le_bytes()[..], &door, &string("Hit")].concat()),
+        property("Target", "InterfaceProperty", &[], &door),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    let parsed = round_trip(&bytes);
+    assert_eq!(parsed["OnHit"], Value::MulticastSparseDelegate(MulticastDelegate(vec![delegate("Hit")])));
+    assert_eq!(parsed["Target"], Value::Interface("/Game/Map.Map:PersistentLevel.Door".to_string()));
+}
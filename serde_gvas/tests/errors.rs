@@ -0,0 +1,80 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use serde_gvas::{ErrorKind, Value, from_slice, to_vec};
+
+use common::{find_string, header};
+
+#[derive(Serialize)]
+struct Item {
+    count: i32,
+}
+
+#[derive(Serialize)]
+struct Inventory {
+    items: Vec<Item>,
+    slots: BTreeMap<String, Item>,
+}
+
+#[derive(Serialize)]
+struct Save {
+    inventory: Inventory,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedItem {
+    #[allow(dead_code)]
+    count: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedInventory {
+    #[allow(dead_code)]
+    items: Vec<NamedItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedSave {
+    #[allow(dead_code)]
+    inventory: NamedInventory,
+}
+
+fn bytes() -> Vec<u8> {
+    let save = Save {
+        inventory: Inventory {
+            items: vec![Item { count: 1 }, Item { count: 2 }],
+            slots: vec![("head".to_string(), Item { count: 3 })].into_iter().collect(),
+        },
+    };
+    to_vec(&header(), &save).unwrap()
+}
+
+#[test]
+fn custom_error() {
+    let bytes = bytes();
+    let e = from_slice::<NamedSave>(&bytes).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Message(_)), "{}", e);
+    assert_eq!(e.path(), "inventory.items[0].count");
+    assert_eq!(e.property_type(), Some("IntProperty"));
+    // the error is raised once the value is read, i.e. after name, type, length, array index, GUID flag and value
+    let end = find_string(&bytes, "count", 0) + 10 + 16 + 4 + 4 + 1 + 4;
+    assert_eq!(e.offset(), Some(end));
+    assert!(e.to_string().ends_with(&format!(" in `inventory.items[0].count` (IntProperty) at offset {}", end)), "{}", e);
+}
+
+#[test]
+fn io_error() {
+    let bytes = bytes();
+    // in the middle of the field of the map entry
+    let end = find_string(&bytes, "count", 2) + 10 + 16 + 4 + 4 + 1 + 2;
+    let e = from_slice::<Value>(&bytes[..end]).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::UnexpectedEof), "{}", e);
+    assert_eq!(e.path(), "inventory.slots[0].count");
+    assert_eq!(e.property_type(), Some("IntProperty"));
+    assert_eq!(e.offset(), Some(end));
+}
@@ -0,0 +1,126 @@
+//! Mutation fuzzing of the sample files, asserting that malformed files never cause a panic
+//!
+//! The mutations are deterministic. Set `GVAS_FUZZ_ITERATIONS` to fuzz longer and `GVAS_FUZZ_SEED`
+//! to try different mutations.
+
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_gvas;
+
+mod common;
+
+use std::collections::HashMap;
+use std::env;
+use std::panic;
+
+use serde::de::IgnoredAny;
+use serde_gvas::{Value, from_slice, to_vec, from_reader_with_header, GvasHeader};
+
+use common::sample;
+
+fn env_var(name: &str, default: u64) -> u64 {
+    env::var(name).ok().and_then(|s| s.parse().ok()).unwrap_or(default)
+}
+
+/// xorshift64*, good enough to pick mutations
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+const INTERESTING_BYTES: &[u8] = &[0, 1, 2, 0x7f, 0x80, 0xfe, 0xff];
+const INTERESTING_INTS: &[i32] = &[0, 1, 2, 3, 4, 8, 16, 255, 256, 0xffff, 0x10000, -1, -2, i32::MIN, i32::MAX];
+
+fn mutate(rng: &mut Rng, bytes: &mut Vec<u8>) {
+    for _ in 0..1 + rng.below(4) {
+        if bytes.is_empty() {
+            return;
+        }
+        let pos = rng.below(bytes.len());
+        match rng.below(6) {
+            0 => bytes[pos] ^= 1 << rng.below(8),
+            1 => bytes[pos] = INTERESTING_BYTES[rng.below(INTERESTING_BYTES.len())],
+            2 => {
+                let int = INTERESTING_INTS[rng.below(INTERESTING_INTS.len())].to_le_bytes();
+                let end = (pos + 4).min(bytes.len());
+                bytes[pos..end].copy_from_slice(&int[..end - pos]);
+            }
+            3 => bytes.truncate(pos),
+            4 => {
+                let end = (pos + 1 + rng.below(16)).min(bytes.len());
+                bytes.drain(pos..end);
+            }
+            _ => {
+                let start = rng.below(bytes.len());
+                let end = (start + 1 + rng.below(16)).min(bytes.len());
+                let chunk = bytes[start..end].to_vec();
+                bytes.splice(pos..pos, chunk);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[allow(dead_code)]
+struct Save {
+    #[serde(default)]
+    new_mmr: i32,
+    #[serde(default)]
+    character_slots: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Slot {
+    #[serde(rename = "DNA", default)]
+    dna: HashMap<String, Value>,
+}
+
+/// Parses the bytes in every way, the results don't matter as long as there is no panic
+fn parse(bytes: &[u8]) {
+    if let Ok((header, value)) = from_reader_with_header::<_, Value>(bytes) {
+        // anything that was read can be written again
+        let _ = to_vec(&header, &value);
+    }
+    let _ = from_slice::<IgnoredAny>(bytes);
+    let _ = from_slice::<HashMap<String, Value>>(bytes);
+    let _ = from_slice::<Save>(bytes);
+}
+
+fn fuzz(name: &str) {
+    let original = sample(name);
+    let iterations = env_var("GVAS_FUZZ_ITERATIONS", 300);
+    let seed = env_var("GVAS_FUZZ_SEED", 0x5eed);
+    let mut rng = Rng(seed);
+    let _: (GvasHeader, Value) = from_reader_with_header(&original[..]).unwrap();
+    for i in 0..iterations {
+        let input_seed = rng.next() | 1;
+        let mut bytes = original.clone();
+        mutate(&mut Rng(input_seed), &mut bytes);
+        if panic::catch_unwind(|| parse(&bytes)).is_err() {
+            panic!("mutation {} of {} with GVAS_FUZZ_SEED={} panicked", i, name, seed);
+        }
+    }
+}
+
+#[test]
+fn fuzz_flai() {
+    fuzz("flai.sav");
+}
+
+#[test]
+fn fuzz_character_slot_save() {
+    fuzz("ChracterSlotSave.9.sav");
+}
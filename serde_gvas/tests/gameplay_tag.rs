@@ -0,0 +1,76 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::collections::HashSet;
+
+use serde_gvas::{GameplayTag, GameplayTagContainer, Value, from_slice, from_value, to_vec};
+
+use common::{header, properties, property, string};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Abilities {
+    tag: GameplayTag,
+    tags: GameplayTagContainer,
+}
+
+fn abilities() -> Abilities {
+    Abilities {
+        tag: GameplayTag("Ability.Attack".to_string()),
+        tags: GameplayTagContainer(vec![GameplayTag("Ability.Attack.Melee".to_string()), GameplayTag("Ability.Block".to_string())]),
+    }
+}
+
+#[test]
+fn wire_format() {
+    let bytes = to_vec(&header(), &abilities()).unwrap();
+    // a GameplayTag is a struct with a single NameProperty
+    let tag = [property("TagName", "NameProperty", &[], &string("Ability.Attack")), string("None")].concat();
+    let mut expected = property("tag", "StructProperty", &[&string("GameplayTag")[..], &[0; 16]].concat(), &tag);
+    // a GameplayTagContainer is native, i.e. the count of tags followed by them
+    let tags = [&2u32.to_le_bytes()[..], &string("Ability.Attack.Melee"), &string("Ability.Block")].concat();
+    expected.extend(property("tags", "StructProperty", &[&string("GameplayTagContainer")[..], &[0; 16]].concat(), &tags));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+}
+
+#[test]
+fn round_trip() {
+    let bytes = to_vec(&header(), &abilities()).unwrap();
+    assert_eq!(from_slice::<Abilities>(&bytes).unwrap(), abilities());
+    // through a `Value`, which holds the container as struct with a GameplayTags field
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value["tags"].get("GameplayTags").map(Value::type_name), Some("ArrayProperty"));
+    assert_eq!(from_value::<Abilities>(value.clone()).unwrap(), abilities());
+    assert_eq!(to_vec(&header(), &value).unwrap(), bytes);
+}
+
+#[test]
+fn empty_container() {
+    let value = Abilities { tag: GameplayTag::default(), tags: GameplayTagContainer::default() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    assert_eq!(from_slice::<Abilities>(&bytes).unwrap(), value);
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Strings {
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct StringSet {
+    tags: HashSet<String>,
+}
+
+#[test]
+fn container_as_strings() {
+    let bytes = to_vec(&header(), &abilities()).unwrap();
+    let strings = Strings { tags: vec!["Ability.Attack.Melee".to_string(), "Ability.Block".to_string()] };
+    let set: HashSet<String> = strings.tags.iter().cloned().collect();
+    assert_eq!(from_slice::<Strings>(&bytes).unwrap(), strings);
+    assert_eq!(from_slice::<StringSet>(&bytes).unwrap().tags, set);
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(from_value::<Strings>(value.clone()).unwrap(), strings);
+    assert_eq!(from_value::<StringSet>(value).unwrap().tags, set);
+}
@@ -0,0 +1,87 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{ErrorKind, Value, from_slice, to_vec};
+
+use common::{header, properties, property, round_trip};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Widths {
+    int8: i8,
+    int16: i16,
+    int: i32,
+    int64: i64,
+    uint16: u16,
+    uint32: u32,
+    uint64: u64,
+    float: f32,
+    double: f64,
+}
+
+fn widths() -> Widths {
+    Widths {
+        int8: i8::MIN,
+        int16: -2,
+        int: i32::MAX,
+        int64: i64::MIN,
+        uint16: u16::MAX,
+        uint32: 3_000_000_000,
+        uint64: u64::MAX,
+        float: 1.5,
+        double: -0.1,
+    }
+}
+
+#[test]
+fn wire_format() {
+    let bytes = to_vec(&header(), &widths()).unwrap();
+    let expected = [
+        property("int8", "Int8Property", &[], &i8::MIN.to_le_bytes()),
+        property("int16", "Int16Property", &[], &(-2i16).to_le_bytes()),
+        property("int", "IntProperty", &[], &i32::MAX.to_le_bytes()),
+        property("int64", "Int64Property", &[], &i64::MIN.to_le_bytes()),
+        property("uint16", "UInt16Property", &[], &u16::MAX.to_le_bytes()),
+        property("uint32", "UInt32Property", &[], &3_000_000_000u32.to_le_bytes()),
+        property("uint64", "UInt64Property", &[], &u64::MAX.to_le_bytes()),
+        property("float", "FloatProperty", &[], &1.5f32.to_le_bytes()),
+        property("double", "DoubleProperty", &[], &(-0.1f64).to_le_bytes()),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Widths>(&bytes).unwrap(), widths());
+    let value = round_trip(&bytes);
+    assert_eq!(value["int8"], Value::Int8(i8::MIN));
+    assert_eq!(value["uint64"], Value::UInt64(u64::MAX));
+    assert_eq!(value["double"], Value::Double(-0.1));
+}
+
+#[test]
+fn qword() {
+    // older games write signed 64 bit integers as QWordProperty
+    let mut bytes = to_vec(&header(), &widths()).unwrap();
+    let typ = common::find_string(&bytes, "Int64Property", 0);
+    bytes.splice(typ..typ + 18, common::string("QWordProperty"));
+    assert_eq!(from_slice::<Widths>(&bytes).unwrap(), widths());
+    assert_eq!(round_trip(&bytes)["int64"], Value::QWord(i64::MIN));
+}
+
+#[test]
+fn invalid_lengths() {
+    let bytes = to_vec(&header(), &widths()).unwrap();
+    for &(name, typ) in &[("int16", "Int16Property"), ("uint32", "UInt32Property"), ("double", "DoubleProperty")] {
+        let mut bytes = bytes.clone();
+        // name, type, then the length
+        let len = common::find_string(&bytes, name, 0) + 4 + name.len() + 1 + 4 + typ.len() + 1;
+        bytes[len] = 3;
+        let e = from_slice::<Value>(&bytes).unwrap_err();
+        match (name, e.kind()) {
+            ("int16", ErrorKind::InvalidInt16Length(3)) | ("uint32", ErrorKind::InvalidUInt32Length(3))
+            | ("double", ErrorKind::InvalidDoubleLength(3)) => (),
+            _ => panic!("{}: {}", name, e),
+        }
+        assert_eq!(e.path(), name);
+        assert_eq!(e.property_type(), Some(typ));
+    }
+}
@@ -0,0 +1,65 @@
+extern crate serde_gvas;
+
+mod common;
+
+use serde_gvas::{DeserializerOptions, Error, ErrorKind, GvasHeader, Guid, Property, Value, from_reader_with_header, from_reader_with_options, to_vec};
+
+use common::{find_string, sample};
+
+fn parse(bytes: &[u8], options: &DeserializerOptions) -> Result<Value, Error> {
+    from_reader_with_options(bytes, options).map(|(_header, value): (GvasHeader, Value)| value)
+}
+
+#[test]
+fn huge_string_length() {
+    let mut bytes = sample("flai.sav");
+    let name = find_string(&bytes, "NewMmr", 0);
+    bytes[name..name + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+    let e = parse(&bytes, &DeserializerOptions::default()).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::StringTooLong(len) if *len == i32::MAX as usize));
+    assert_eq!(e.offset(), Some(name));
+}
+
+#[test]
+fn max_string_length() {
+    let bytes = sample("flai.sav");
+    let options = DeserializerOptions { max_string_length: 24, ..DeserializerOptions::default() };
+    let e = parse(&bytes, &options).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::StringTooLong(len) if *len > 24));
+}
+
+#[test]
+fn max_element_count() {
+    let bytes = sample("ChracterSlotSave.9.sav");
+    let options = DeserializerOptions { max_element_count: 2, ..DeserializerOptions::default() };
+    let e = parse(&bytes, &options).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::TooManyElements(3)));
+    assert_eq!(e.path(), "CharacterSlots");
+}
+
+#[test]
+fn max_total_bytes() {
+    let bytes = sample("flai.sav");
+    let options = DeserializerOptions { max_total_bytes: bytes.len(), ..DeserializerOptions::default() };
+    assert!(parse(&bytes, &options).is_ok());
+    let options = DeserializerOptions { max_total_bytes: bytes.len() - 1, ..DeserializerOptions::default() };
+    let e = parse(&bytes, &options).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::TooManyBytes(limit) if *limit == bytes.len() - 1));
+    assert_eq!(e.offset(), Some(bytes.len() - 1));
+}
+
+#[test]
+fn max_depth() {
+    let (header, _): (GvasHeader, Value) = from_reader_with_header(&sample("flai.sav")[..]).unwrap();
+    let mut value = Value::Struct { type_name: "Leaf".to_string(), guid: Guid::default(), properties: Vec::new() };
+    for _ in 0..30 {
+        let properties = vec![Property::new("Inner", value)];
+        value = Value::Struct { type_name: "Node".to_string(), guid: Guid::default(), properties };
+    }
+    let bytes = to_vec(&header, &value).unwrap();
+    assert!(parse(&bytes, &DeserializerOptions::default()).is_ok());
+    let options = DeserializerOptions { max_depth: 20, ..DeserializerOptions::default() };
+    let e = parse(&bytes, &options).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::NestingTooDeep(20)));
+    assert!(e.path().starts_with("Inner.Inner."));
+}
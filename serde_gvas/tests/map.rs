@@ -0,0 +1,108 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_gvas::{DeserializerOptions, Guid, IntPoint, Property, Value, Vector, from_slice, from_value, to_vec};
+
+use common::{header, properties, property, round_trip, round_trip_with_options, string};
+
+fn save(properties: Vec<Property>) -> Value {
+    Value::Struct { type_name: String::new(), guid: Guid::default(), properties }
+}
+
+#[test]
+fn removed_keys() {
+    let map = Value::Map {
+        key_type: "StrProperty".to_string(),
+        value_type: "IntProperty".to_string(),
+        removed: vec![Value::Str("gone".to_string())],
+        entries: vec![(Value::Str("a".to_string()), Value::Int(1)), (Value::Str("b".to_string()), Value::Int(2))],
+    };
+    let bytes = to_vec(&header(), &save(vec![Property::new("Counters", map.clone())])).unwrap();
+    // the count of removed keys and the keys, then the count of entries and the entries
+    let body = [
+        &1u32.to_le_bytes()[..], &string("gone"),
+        &2u32.to_le_bytes(), &string("a"), &1i32.to_le_bytes(), &string("b"), &2i32.to_le_bytes(),
+    ].concat();
+    let expected = property("Counters", "MapProperty", &[string("StrProperty"), string("IntProperty")].concat(), &body);
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(round_trip(&bytes)["Counters"], map);
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Stats {
+    level: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    counters: BTreeMap<String, f32>,
+    stats: BTreeMap<i32, Stats>,
+}
+
+#[test]
+fn typed_map() {
+    let value = Save {
+        counters: vec![("x".to_string(), 0.5)].into_iter().collect(),
+        stats: vec![(7, Stats { level: 3 })].into_iter().collect(),
+    };
+    let bytes = to_vec(&header(), &value).unwrap();
+    let counters = [&0u32.to_le_bytes()[..], &1u32.to_le_bytes(), &string("x"), &0.5f32.to_le_bytes()].concat();
+    let mut expected = property("counters", "MapProperty", &[string("StrProperty"), string("FloatProperty")].concat(), &counters);
+    // struct values are their properties without a tag, as the map only knows they are structs
+    let stats = [
+        &0u32.to_le_bytes()[..], &1u32.to_le_bytes(), &7i32.to_le_bytes(),
+        &property("level", "IntProperty", &[], &3i32.to_le_bytes()), &string("None"),
+    ].concat();
+    expected.extend(property("stats", "MapProperty", &[string("IntProperty"), string("StructProperty")].concat(), &stats));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), value);
+    round_trip(&bytes);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Positions {
+    positions: BTreeMap<String, Vector>,
+    cells: HashMap<IntPoint, i32>,
+}
+
+#[test]
+fn native_structs() {
+    let value = Positions {
+        positions: vec![("spawn".to_string(), Vector { x: 1.0, y: 2.0, z: 3.0 })].into_iter().collect(),
+        cells: vec![(IntPoint { x: 4, y: 5 }, 6)].into_iter().collect(),
+    };
+    let bytes = to_vec(&header(), &value).unwrap();
+    // like in files written by Unreal, native structs are their raw fields without a struct type
+    let vector = [1.0f32.to_le_bytes(), 2.0f32.to_le_bytes(), 3.0f32.to_le_bytes()].concat();
+    let positions = [&0u32.to_le_bytes()[..], &1u32.to_le_bytes(), &string("spawn"), &vector].concat();
+    let cells = [&0u32.to_le_bytes()[..], &1u32.to_le_bytes(), &4i32.to_le_bytes(), &5i32.to_le_bytes(), &6i32.to_le_bytes()].concat();
+    let expected = [
+        property("positions", "MapProperty", &[string("StrProperty"), string("StructProperty")].concat(), &positions),
+        property("cells", "MapProperty", &[string("StructProperty"), string("IntProperty")].concat(), &cells),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Positions>(&bytes).unwrap(), value);
+
+    // `Value` needs to be told the struct types
+    assert!(from_slice::<Value>(&bytes).is_err());
+    let mut options = DeserializerOptions::default();
+    options.struct_types.insert("positions.Value".to_string(), "Vector".to_string());
+    options.struct_types.insert("cells.Key".to_string(), "IntPoint".to_string());
+    let parsed = round_trip_with_options(&bytes, &options);
+    assert_eq!(parsed["positions"], Value::Map {
+        key_type: "StrProperty".to_string(),
+        value_type: "StructProperty".to_string(),
+        removed: Vec::new(),
+        entries: vec![(Value::Str("spawn".to_string()), Value::Struct {
+            type_name: "Vector".to_string(),
+            guid: Guid::default(),
+            properties: vec![Property::new("X", Value::Float(1.0)), Property::new("Y", Value::Float(2.0)), Property::new("Z", Value::Float(3.0))],
+        })],
+    });
+    assert_eq!(from_value::<Positions>(parsed).unwrap(), value);
+}
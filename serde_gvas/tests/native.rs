@@ -0,0 +1,108 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{BoundingBox, Color, DeserializerOptions, GvasHeader, Rotator, Transform, Value, Vector, from_slice, from_value, to_vec};
+
+use common::{header, header_ue5, properties, property, round_trip, round_trip_with_options, string};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    location: Vector,
+    rotation: Rotator,
+    color: Color,
+    bounds: BoundingBox,
+}
+
+fn save() -> Save {
+    Save {
+        location: Vector { x: 1.5, y: -2.0, z: 0.25 },
+        rotation: Rotator { pitch: 90.0, yaw: 0.0, roll: -45.0 },
+        color: Color { b: 1, g: 2, r: 3, a: 255 },
+        bounds: BoundingBox { min: Vector { x: -1.0, y: -1.0, z: -1.0 }, max: Vector { x: 1.0, y: 1.0, z: 1.0 }, is_valid: true },
+    }
+}
+
+fn struct_type(name: &str) -> Vec<u8> {
+    [&string(name)[..], &[0; 16]].concat()
+}
+
+/// Encodes the numbers as floats before UE5 and as doubles afterwards
+fn reals(ue5: bool, reals: &[f64]) -> Vec<u8> {
+    reals.iter().flat_map(|&real| if ue5 { real.to_le_bytes().to_vec() } else { (real as f32).to_le_bytes().to_vec() }).collect()
+}
+
+fn expected(ue5: bool) -> Vec<u8> {
+    let bounds = [reals(ue5, &[-1.0, -1.0, -1.0, 1.0, 1.0, 1.0]), vec![1]].concat();
+    [
+        property("location", "StructProperty", &struct_type("Vector"), &reals(ue5, &[1.5, -2.0, 0.25])),
+        property("rotation", "StructProperty", &struct_type("Rotator"), &reals(ue5, &[90.0, 0.0, -45.0])),
+        property("color", "StructProperty", &struct_type("Color"), &[1, 2, 3, 255]),
+        property("bounds", "StructProperty", &struct_type("Box"), &bounds),
+    ].concat()
+}
+
+#[test]
+fn floats() {
+    let bytes = to_vec(&header(), &save()).unwrap();
+    assert_eq!(properties(&header(), &bytes), &expected(false)[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), save());
+    assert_eq!(round_trip(&bytes)["location"]["X"], Value::Float(1.5));
+}
+
+#[test]
+fn large_world_coordinates() {
+    let bytes = to_vec(&header_ue5(), &save()).unwrap();
+    assert_eq!(properties(&header_ue5(), &bytes), &expected(true)[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), save());
+    assert_eq!(round_trip(&bytes)["location"]["X"], Value::Double(1.5));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Actor {
+    transform: Transform,
+}
+
+#[test]
+fn transform() {
+    // a transform is a list of properties of native structs
+    let actor = Actor { transform: Transform { translation: Vector { x: 1.0, y: 2.0, z: 3.0 }, ..Transform::default() } };
+    let bytes = to_vec(&header(), &actor).unwrap();
+    let fields = [
+        property("Rotation", "StructProperty", &struct_type("Quat"), &reals(false, &[0.0, 0.0, 0.0, 1.0])),
+        property("Translation", "StructProperty", &struct_type("Vector"), &reals(false, &[1.0, 2.0, 3.0])),
+        property("Scale3D", "StructProperty", &struct_type("Vector"), &reals(false, &[1.0, 1.0, 1.0])),
+        string("None"),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &property("transform", "StructProperty", &struct_type("Transform"), &fields)[..]);
+    assert_eq!(from_slice::<Actor>(&bytes).unwrap(), actor);
+    round_trip(&bytes);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Path {
+    points: Vec<Vector>,
+}
+
+#[test]
+fn array_without_inner_tag() {
+    // before version 500, arrays of structs don't have a tag with the struct type of their elements
+    let header = GvasHeader { package_version: 498, ..header() };
+    let value = Path { points: vec![Vector { x: 1.0, y: 2.0, z: 3.0 }, Vector { x: 4.0, y: 5.0, z: 6.0 }] };
+    let bytes = to_vec(&header, &value).unwrap();
+    let points = [&2u32.to_le_bytes()[..], &reals(false, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])].concat();
+    // without property GUIDs either
+    let expected = [&string("points")[..], &string("ArrayProperty"), &(points.len() as u32).to_le_bytes(), &[0; 4], &string("StructProperty"), &points].concat();
+    assert_eq!(properties(&header, &bytes), &expected[..]);
+    assert_eq!(from_slice::<Path>(&bytes).unwrap(), value);
+
+    // `Value` needs to be told the struct type
+    assert!(from_slice::<Value>(&bytes).is_err());
+    let mut options = DeserializerOptions::default();
+    options.struct_types.insert("points".to_string(), "Vector".to_string());
+    let parsed = round_trip_with_options(&bytes, &options);
+    assert_eq!(parsed["points"][1]["Z"], Value::Float(6.0));
+    assert_eq!(from_value::<Path>(parsed).unwrap(), value);
+}
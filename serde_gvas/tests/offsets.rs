@@ -0,0 +1,68 @@
+extern crate serde_gvas;
+
+mod common;
+
+use serde_gvas::{Error, ErrorKind, Value, from_slice};
+
+use common::{find_string, sample};
+
+fn parse_error(bytes: &[u8]) -> Error {
+    from_slice::<Value>(bytes).unwrap_err()
+}
+
+#[test]
+fn invalid_magic() {
+    let mut bytes = sample("flai.sav");
+    bytes[0] = b'X';
+    let e = parse_error(&bytes);
+    assert!(matches!(e.kind(), ErrorKind::InvalidMagic(_)));
+    assert_eq!(e.offset(), Some(0));
+    assert_eq!(e.path(), "");
+}
+
+#[test]
+fn invalid_int_length() {
+    let mut bytes = sample("flai.sav");
+    // name, type, length, array index, value
+    let len = find_string(&bytes, "NewMmr", 0) + 11 + 16;
+    bytes[len] = 5;
+    let e = parse_error(&bytes);
+    assert!(matches!(e.kind(), ErrorKind::InvalidIntLength(5)));
+    assert_eq!(e.offset(), Some(len + 8));
+    assert_eq!(e.path(), "NewMmr");
+    assert_eq!(e.property_type(), Some("IntProperty"));
+}
+
+#[test]
+fn string_not_zero_terminated() {
+    let mut bytes = sample("flai.sav");
+    let typ = find_string(&bytes, "IntProperty", 0);
+    bytes[typ + 4 + 11] = b'!';
+    let e = parse_error(&bytes);
+    assert!(matches!(e.kind(), ErrorKind::StringNotZeroTerminated(_)));
+    assert_eq!(e.offset(), Some(typ));
+}
+
+#[test]
+fn truncated() {
+    let bytes = sample("flai.sav");
+    // in the middle of the value of NewMmr
+    let end = find_string(&bytes, "NewMmr", 0) + 11 + 16 + 8 + 2;
+    let e = parse_error(&bytes[..end]);
+    assert!(matches!(e.kind(), ErrorKind::UnexpectedEof));
+    assert_eq!(e.offset(), Some(end));
+    assert_eq!(e.path(), "NewMmr");
+}
+
+#[test]
+fn nested_property() {
+    let mut bytes = sample("ChracterSlotSave.9.sav");
+    let len = find_string(&bytes, "SkinTone", 1) + 13 + 18;
+    bytes[len] = 8;
+    let e = parse_error(&bytes);
+    assert!(matches!(e.kind(), ErrorKind::InvalidFloatLength(8)));
+    assert_eq!(e.offset(), Some(len + 8));
+    assert_eq!(e.path(), "CharacterSlots[1].DNA.SkinTone");
+    assert_eq!(e.property_type(), Some("FloatProperty"));
+    assert_eq!(e.to_string(), format!("invalid float length 8 in `CharacterSlots[1].DNA.SkinTone` (FloatProperty) at offset {}", len + 8));
+}
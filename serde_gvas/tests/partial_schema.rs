@@ -0,0 +1,91 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{ErrorKind, Value, from_slice};
+
+use common::{find_string, round_trip, sample};
+
+fn replace_string(bytes: &mut [u8], s: &str, replacement: &str) {
+    assert_eq!(s.len(), replacement.len());
+    let offset = find_string(bytes, s, 0) + 4;
+    bytes[offset..offset + s.len()].copy_from_slice(replacement.as_bytes());
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Mmr {
+    new_mmr: i32,
+    new_mmr_uncertainty: f32,
+}
+
+#[test]
+fn unknown_type_is_preserved() {
+    let mut bytes = sample("flai.sav");
+    // the type of NewMmr
+    let typ = find_string(&bytes, "NewMmr", 0) + 11 + 4;
+    bytes[typ] = b'X';
+    let value = round_trip(&bytes);
+    let unknown = Value::Unknown {
+        type_name: "XntProperty".to_string(),
+        inner_type: None,
+        value_type: None,
+        bytes: 1277i32.to_le_bytes().to_vec(),
+    };
+    assert_eq!(value["NewMmr"], unknown);
+    assert_eq!(value["NewMmrUncertainty"], Value::Float(3.8680062));
+    let e = from_slice::<Mmr>(&bytes).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Message(_)));
+    assert_eq!(e.path(), "NewMmr");
+}
+
+#[test]
+fn unknown_inner_type_is_preserved() {
+    let mut bytes = sample("flai.sav");
+    replace_string(&mut bytes, "StructProperty", "StructPropertX");
+    let value = round_trip(&bytes);
+    match value["CharacterSlots"] {
+        Value::Unknown { ref type_name, ref inner_type, value_type: None, ref bytes } => {
+            assert_eq!(type_name, "ArrayProperty");
+            assert_eq!(inner_type.as_deref(), Some("StructPropertX"));
+            // the count of slots
+            assert_eq!(&bytes[..4], &[3, 0, 0, 0]);
+        }
+        ref value => panic!("unexpected value {:?}", value),
+    }
+    assert_eq!(value["NewMmr"], Value::Int(1277));
+}
+
+#[test]
+fn ignored_properties_are_skipped() {
+    let mut bytes = sample("flai.sav");
+    // corrupt the first float within CharacterSlots, which is ignored
+    let len = find_string(&bytes, "FloatProperty", 0) + 18;
+    bytes[len] = 5;
+    let e = from_slice::<Value>(&bytes).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::InvalidFloatLength(5)));
+    let mmr: Mmr = from_slice(&bytes).unwrap();
+    assert_eq!(mmr.new_mmr, 1277);
+    assert_eq!(mmr.new_mmr_uncertainty, 3.8680062);
+}
+
+#[test]
+fn size_mismatch() {
+    let mut bytes = sample("flai.sav");
+    // name, type, length
+    let len = find_string(&bytes, "CharacterSlots", 0) + 19 + 18;
+    let declared = u32::from_le_bytes([bytes[len], bytes[len + 1], bytes[len + 2], bytes[len + 3]]);
+    bytes[len..len + 4].copy_from_slice(&(declared + 1).to_le_bytes());
+    let e = from_slice::<Value>(&bytes).unwrap_err();
+    match *e.kind() {
+        ErrorKind::SizeMismatch { expected, actual } => {
+            assert_eq!(expected, declared + 1);
+            assert_eq!(actual, declared as usize);
+        }
+        ref kind => panic!("unexpected error {:?}", kind),
+    }
+    assert_eq!(e.path(), "CharacterSlots");
+    assert_eq!(e.offset(), Some(len + 8 + 19));
+}
@@ -0,0 +1,78 @@
+extern crate serde_gvas;
+
+mod common;
+
+use serde_gvas::{Guid, GvasHeader, LinearColor, Property, StructType, Value, from_reader_with_header, from_value, to_vec};
+
+use common::{header, properties, property, sample, string};
+
+fn assert_round_trip(bytes: &[u8]) -> (GvasHeader, Value) {
+    let (header, value): (GvasHeader, Value) = from_reader_with_header(bytes).unwrap();
+    let written = to_vec(&header, &value).unwrap();
+    assert!(written == bytes, "written file differs from the original");
+    (header, value)
+}
+
+#[test]
+fn flai() {
+    let (header, value) = assert_round_trip(&sample("flai.sav"));
+    assert_eq!(header.save_game_class_name, "LocalCharacterSlotSave");
+    assert_eq!(value["NewMmr"], Value::Int(1277));
+}
+
+#[test]
+fn character_slot_save() {
+    let (header, value) = assert_round_trip(&sample("ChracterSlotSave.9.sav"));
+    assert_eq!(header.trailing_bytes, vec![0, 0, 0, 0]);
+    assert_eq!(value["NewMmr"], Value::Int(-173));
+    assert_eq!(value["CharacterSlots"][0]["DNA"]["HairColor"]["A"], Value::Float(1.0));
+    let hair_color: LinearColor = from_value(value["CharacterSlots"][0]["DNA"]["HairColor"].clone()).unwrap();
+    assert_eq!(hair_color.a, 1.0);
+}
+
+#[test]
+fn newer_package_version() {
+    let (mut header, mut value) = assert_round_trip(&sample("flai.sav"));
+    // struct GUIDs, property GUIDs and tags in front of arrays of structs
+    header.package_version = 522;
+    header.trailing_bytes = vec![0, 0, 0, 0, 42];
+    let guid: Guid = "01234567-89AB-CDEF-0123-456789ABCDEF".parse().unwrap();
+    if let Value::Struct { ref mut properties, .. } = value {
+        properties.push(Property { name: "Static".to_string(), array_index: 0, guid: None, value: Value::Int(1) });
+        properties.push(Property { name: "Static".to_string(), array_index: 1, guid: Some(guid), value: Value::Int(2) });
+        properties.push(Property::new("Empty", Value::Array {
+            inner_type: "StructProperty".to_string(),
+            struct_type: Some(StructType { name: "Vector".to_string(), guid }),
+            elements: Vec::new(),
+        }));
+        properties.push(Property::new("WithGuid", Value::Struct {
+            type_name: "Custom".to_string(),
+            guid,
+            properties: vec![Property::new("Flag", Value::Bool(true))],
+        }));
+    }
+    let bytes = to_vec(&header, &value).unwrap();
+    let (read_header, read_value) = assert_round_trip(&bytes);
+    assert_eq!(read_header, header);
+    assert_eq!(read_value["Empty"], value["Empty"]);
+    assert_eq!(read_value["WithGuid"], value["WithGuid"]);
+}
+
+#[test]
+fn non_canonical_strings() {
+    // the encoding of strings isn't kept, so these are written back as ASCII and length 0
+    let utf16 = [&(-3i32).to_le_bytes()[..], &[b'H', 0, b'i', 0, 0, 0]].concat();
+    let terminator = [&1i32.to_le_bytes()[..], &[0]].concat();
+    let mut bytes = Vec::new();
+    header().write(&mut bytes).unwrap();
+    bytes.extend(property("Utf16", "StrProperty", &[], &utf16));
+    bytes.extend(property("Terminator", "StrProperty", &[], &terminator));
+    bytes.extend(string("None"));
+    bytes.extend_from_slice(&[0; 4]);
+    let (header, value): (GvasHeader, Value) = from_reader_with_header(&bytes[..]).unwrap();
+    assert_eq!(value["Utf16"], Value::Str("Hi".to_string()));
+    assert_eq!(value["Terminator"], Value::Str(String::new()));
+    let written = to_vec(&header, &value).unwrap();
+    let expected = [property("Utf16", "StrProperty", &[], &string("Hi")), property("Terminator", "StrProperty", &[], &string(""))].concat();
+    assert_eq!(properties(&header, &written), &expected[..]);
+}
@@ -0,0 +1,175 @@
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_gvas;
+
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use serde::ser::{self, SerializeMap};
+use serde_gvas::{ErrorKind, Guid, Property, Serializer, Value, from_slice, to_vec};
+
+use common::{header, properties, property, string};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Optional {
+    some: Option<i32>,
+    none: Option<i32>,
+}
+
+#[test]
+fn option() {
+    let value = Optional { some: Some(4), none: None };
+    let bytes = to_vec(&header(), &value).unwrap();
+    // `None` isn't written, which is how missing properties are read back
+    assert_eq!(properties(&header(), &bytes), &property("some", "IntProperty", &[], &4i32.to_le_bytes())[..]);
+    assert_eq!(from_slice::<Optional>(&bytes).unwrap(), value);
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Item {
+    count: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Containers {
+    #[serde(with = "serde_gvas::typed")]
+    ints: Vec<i32>,
+    #[serde(with = "serde_gvas::typed")]
+    items: Vec<Item>,
+    #[serde(with = "serde_gvas::typed::set")]
+    names: HashSet<String>,
+    #[serde(with = "serde_gvas::typed")]
+    counters: HashMap<String, i32>,
+}
+
+#[test]
+fn empty_containers() {
+    let value = Containers { ints: Vec::new(), items: Vec::new(), names: HashSet::new(), counters: HashMap::new() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    let mut expected = property("ints", "ArrayProperty", &string("IntProperty"), &[0; 4]);
+    // arrays of structs have a tag for their elements even when empty
+    let mut items = vec![0; 4];
+    items.extend(property("items", "StructProperty", &[&string("Item")[..], &[0; 16]].concat(), &[]));
+    expected.extend(property("items", "ArrayProperty", &string("StructProperty"), &items));
+    // no removed elements and no elements
+    expected.extend(property("names", "SetProperty", &string("StrProperty"), &[0; 8]));
+    expected.extend(property("counters", "MapProperty", &[string("StrProperty"), string("IntProperty")].concat(), &[0; 8]));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Containers>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn typed_containers() {
+    let value = Containers {
+        ints: vec![1, 2],
+        items: vec![Item { count: 3 }],
+        names: vec!["a".to_string()].into_iter().collect(),
+        counters: vec![("b".to_string(), 4)].into_iter().collect(),
+    };
+    let bytes = to_vec(&header(), &value).unwrap();
+    assert_eq!(from_slice::<Containers>(&bytes).unwrap(), value);
+    // the default elements only determine the types
+    let value: Value = from_slice(&bytes).unwrap();
+    let elements = vec![Value::Int(1), Value::Int(2)];
+    assert_eq!(value["ints"], Value::Array { inner_type: "IntProperty".to_string(), struct_type: None, elements });
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Untyped {
+    ints: Vec<String>,
+    items: Vec<Item>,
+    counters: HashMap<String, Item>,
+}
+
+#[test]
+fn empty_containers_without_types() {
+    let value = Untyped { ints: Vec::new(), items: Vec::new(), counters: HashMap::new() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    // without elements the element types default to IntProperty
+    let mut expected = property("ints", "ArrayProperty", &string("IntProperty"), &[0; 4]);
+    expected.extend(property("items", "ArrayProperty", &string("IntProperty"), &[0; 4]));
+    expected.extend(property("counters", "MapProperty", &[string("IntProperty"), string("IntProperty")].concat(), &[0; 8]));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Untyped>(&bytes).unwrap(), value);
+    // the elements reveal the types
+    let value = Untyped { ints: vec!["a".to_string()], items: Vec::new(), counters: HashMap::new() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    let expected = property("ints", "ArrayProperty", &string("StrProperty"), &[&1u32.to_le_bytes()[..], &string("a")].concat());
+    assert_eq!(&properties(&header(), &bytes)[..expected.len()], &expected[..]);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    name: String,
+    item: Option<Item>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Outer {
+    inner: Inner,
+    inners: Vec<Inner>,
+}
+
+#[test]
+fn nested_structs() {
+    let value = Outer {
+        inner: Inner { name: "first".to_string(), item: Some(Item { count: 1 }) },
+        inners: vec![
+            Inner { name: "second".to_string(), item: None },
+            Inner { name: "third".to_string(), item: Some(Item { count: 2 }) },
+        ],
+    };
+    let bytes = to_vec(&header(), &value).unwrap();
+    assert_eq!(from_slice::<Outer>(&bytes).unwrap(), value);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "ESlotState")]
+enum SlotState {
+    Empty,
+    Occupied,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Slot {
+    state: SlotState,
+    states: Vec<SlotState>,
+}
+
+#[test]
+fn enum_property() {
+    let value = Slot { state: SlotState::Occupied, states: vec![SlotState::Empty] };
+    let bytes = to_vec(&header(), &value).unwrap();
+    let mut expected = property("state", "EnumProperty", &string("ESlotState"), &string("ESlotState::Occupied"));
+    let states = [&1u32.to_le_bytes()[..], &string("ESlotState::Empty")].concat();
+    expected.extend(property("states", "ArrayProperty", &string("EnumProperty"), &states));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Slot>(&bytes).unwrap(), value);
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value["state"], Value::Enum { enum_name: "ESlotState".to_string(), variant: "ESlotState::Occupied".to_string() });
+    // elements don't have an enum name of their own, it's the prefix of their variant
+    assert_eq!(value["states"][0], Value::Enum { enum_name: "ESlotState".to_string(), variant: "ESlotState::Empty".to_string() });
+    assert_eq!(to_vec(&header(), &value).unwrap(), bytes);
+}
+
+#[test]
+fn byte_property_enum() {
+    // ByteProperties holding an enum variant can be read into Rust enums as well
+    let state = Value::Byte { enum_name: "ESlotState".to_string(), variant: "ESlotState::Empty".to_string() };
+    let states = Value::Array { inner_type: "ByteProperty".to_string(), struct_type: None, elements: Vec::new() };
+    let properties = vec![Property::new("state", state), Property::new("states", states)];
+    let value = Value::Struct { type_name: String::new(), guid: Guid::default(), properties };
+    let bytes = to_vec(&header(), &value).unwrap();
+    assert_eq!(from_slice::<Slot>(&bytes).unwrap(), Slot { state: SlotState::Empty, states: Vec::new() });
+}
+
+#[test]
+fn value_without_key() {
+    let mut bytes = Vec::new();
+    let mut ser = Serializer::new(&mut bytes, &header());
+    let mut map = ser::Serializer::serialize_map(&mut ser, None).unwrap();
+    let e = map.serialize_value(&1).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Message(_)));
+}
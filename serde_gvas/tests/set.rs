@@ -0,0 +1,76 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::collections::BTreeSet;
+
+use serde_gvas::{DeserializerOptions, Guid, IntPoint, Property, Value, from_slice, from_value, to_vec};
+
+use common::{header, properties, property, round_trip, round_trip_with_options, string};
+
+#[test]
+fn removed_elements() {
+    let set = Value::Set {
+        inner_type: "NameProperty".to_string(),
+        removed: vec![Value::Name("Old".to_string())],
+        elements: vec![Value::Name("New".to_string())],
+    };
+    let save = Value::Struct { type_name: String::new(), guid: Guid::default(), properties: vec![Property::new("Unlocked", set.clone())] };
+    let bytes = to_vec(&header(), &save).unwrap();
+    // the count of removed elements and the elements, then the count of elements and the elements
+    let body = [&1u32.to_le_bytes()[..], &string("Old"), &1u32.to_le_bytes(), &string("New")].concat();
+    let expected = property("Unlocked", "SetProperty", &string("NameProperty"), &body);
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(round_trip(&bytes)["Unlocked"], set);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    #[serde(with = "serde_gvas::set")]
+    unlocked: BTreeSet<i32>,
+    #[serde(with = "serde_gvas::typed::set")]
+    visited: BTreeSet<String>,
+}
+
+#[test]
+fn typed_set() {
+    let value = Save { unlocked: vec![2, 1].into_iter().collect(), visited: BTreeSet::new() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    let unlocked = [&0u32.to_le_bytes()[..], &2u32.to_le_bytes(), &1i32.to_le_bytes(), &2i32.to_le_bytes()].concat();
+    let mut expected = property("unlocked", "SetProperty", &string("IntProperty"), &unlocked);
+    expected.extend(property("visited", "SetProperty", &string("StrProperty"), &[0; 8]));
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), value);
+    round_trip(&bytes);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Cells {
+    #[serde(with = "serde_gvas::set")]
+    cells: Vec<IntPoint>,
+}
+
+#[test]
+fn native_structs() {
+    let value = Cells { cells: vec![IntPoint { x: 1, y: 2 }, IntPoint { x: 3, y: 4 }] };
+    let bytes = to_vec(&header(), &value).unwrap();
+    // like in files written by Unreal, native structs are their raw fields without a struct type
+    let cells = [0u32, 2, 1, 2, 3, 4].iter().flat_map(|i| i.to_le_bytes().to_vec()).collect::<Vec<_>>();
+    let expected = property("cells", "SetProperty", &string("StructProperty"), &cells);
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    assert_eq!(from_slice::<Cells>(&bytes).unwrap(), value);
+
+    // `Value` needs to be told the struct type
+    assert!(from_slice::<Value>(&bytes).is_err());
+    let mut options = DeserializerOptions::default();
+    options.struct_types.insert("cells".to_string(), "IntPoint".to_string());
+    let parsed = round_trip_with_options(&bytes, &options);
+    assert_eq!(parsed["cells"][1], Value::Struct {
+        type_name: "IntPoint".to_string(),
+        guid: Guid::default(),
+        properties: vec![Property::new("X", Value::Int(3)), Property::new("Y", Value::Int(4))],
+    });
+    assert_eq!(from_value::<Cells>(parsed).unwrap(), value);
+}
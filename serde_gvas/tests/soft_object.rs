@@ -0,0 +1,63 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{Guid, Property, SoftObjectPath, Value, from_slice, to_vec};
+
+use common::{header, header_ue5, properties, property, round_trip, string};
+
+fn path() -> SoftObjectPath {
+    SoftObjectPath { asset_path_name: "/Game/Maps/Level.Level".to_string(), sub_path_string: "PersistentLevel.Actor".to_string() }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    path: SoftObjectPath,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Strings {
+    path: String,
+}
+
+#[test]
+fn asset_path_name() {
+    let bytes = to_vec(&header(), &Save { path: path() }).unwrap();
+    let body = [string("/Game/Maps/Level.Level"), string("PersistentLevel.Actor")].concat();
+    assert_eq!(properties(&header(), &bytes), &property("path", "SoftObjectProperty", &[], &body)[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), Save { path: path() });
+    assert_eq!(from_slice::<Strings>(&bytes).unwrap().path, "/Game/Maps/Level.Level:PersistentLevel.Actor");
+    round_trip(&bytes);
+}
+
+#[test]
+fn package_and_asset_name() {
+    // UE5 stores the package and the asset separately
+    let bytes = to_vec(&header_ue5(), &Save { path: path() }).unwrap();
+    let body = [string("/Game/Maps/Level"), string("Level"), string("PersistentLevel.Actor")].concat();
+    assert_eq!(properties(&header_ue5(), &bytes), &property("path", "SoftObjectProperty", &[], &body)[..]);
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), Save { path: path() });
+    round_trip(&bytes);
+}
+
+#[test]
+fn soft_class_and_lazy_object() {
+    let guid: Guid = "01234567-89AB-CDEF-0123-456789ABCDEF".parse().unwrap();
+    let class = SoftObjectPath { asset_path_name: "/Game/Item.Item_C".to_string(), sub_path_string: String::new() };
+    let save = Value::Struct {
+        type_name: String::new(),
+        guid: Guid::default(),
+        properties: vec![Property::new("Class", Value::SoftClass(class.clone())), Property::new("Object", Value::LazyObject(guid))],
+    };
+    let bytes = to_vec(&header(), &save).unwrap();
+    let expected = [
+        property("Class", "SoftClassProperty", &[], &[string("/Game/Item.Item_C"), string("")].concat()),
+        property("Object", "LazyObjectProperty", &[], &guid.0),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+    let parsed = round_trip(&bytes);
+    assert_eq!(parsed["Class"], Value::SoftClass(class));
+    assert_eq!(parsed["Object"], Value::LazyObject(guid));
+}
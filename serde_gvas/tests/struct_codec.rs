@@ -0,0 +1,106 @@
+extern crate byteorder;
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use std::io::{Read, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use serde_gvas::{ErrorKind, GvasHeader, Property, StructCodecRegistry, Value, from_reader_with_codecs, to_writer_with_codecs};
+
+use common::{header, properties, property, string};
+
+/// Registers the codec of a struct whose body is its seed as i32, decoding `read` bytes of it
+fn register(codecs: &mut StructCodecRegistry, name: &str, read: usize) {
+    codecs.register(name, move |r: &mut dyn Read| {
+        let mut seed = [0; 4];
+        r.read_exact(&mut seed[..read])?;
+        Ok(vec![Property::new("seed", Value::Int((&seed[..]).read_i32::<LE>()?))])
+    }, |fields: &[Property], w: &mut dyn Write| {
+        match fields[0].value {
+            Value::Int(seed) => w.write_i32::<LE>(seed)?,
+            ref value => panic!("{:?}", value),
+        }
+        Ok(())
+    });
+}
+
+/// Codecs of `Dna` and `Gene` decoding the given number of bytes of their body
+fn codecs(dna: usize, gene: usize) -> StructCodecRegistry {
+    let mut codecs = StructCodecRegistry::new();
+    register(&mut codecs, "Dna", dna);
+    register(&mut codecs, "Gene", gene);
+    codecs
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Dna {
+    seed: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Gene {
+    seed: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    dna: Dna,
+    genes: Vec<Gene>,
+}
+
+fn save() -> Save {
+    Save { dna: Dna { seed: 1 }, genes: vec![Gene { seed: 2 }, Gene { seed: 3 }] }
+}
+
+fn bytes() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    to_writer_with_codecs(&mut bytes, &header(), &codecs(4, 4), &save()).unwrap();
+    bytes
+}
+
+#[test]
+fn wire_format() {
+    let mut expected = property("dna", "StructProperty", &[&string("Dna")[..], &[0; 16]].concat(), &1i32.to_le_bytes());
+    // the inner tag of the elements has the length of all of them
+    let elements = [&2i32.to_le_bytes()[..], &3i32.to_le_bytes()].concat();
+    let inner_tag = property("genes", "StructProperty", &[&string("Gene")[..], &[0; 16]].concat(), &elements);
+    let genes = [&2u32.to_le_bytes()[..], &inner_tag].concat();
+    expected.extend(property("genes", "ArrayProperty", &string("StructProperty"), &genes));
+    assert_eq!(properties(&header(), &bytes()), &expected[..]);
+}
+
+#[test]
+fn round_trip() {
+    let bytes = bytes();
+    let (_, typed): (GvasHeader, Save) = from_reader_with_codecs(&bytes[..], &codecs(4, 4)).unwrap();
+    assert_eq!(typed, save());
+    let (header, value): (GvasHeader, Value) = from_reader_with_codecs(&bytes[..], &codecs(4, 4)).unwrap();
+    assert_eq!(value["genes"][1]["seed"], Value::Int(3));
+    let mut written = Vec::new();
+    to_writer_with_codecs(&mut written, &header, &codecs(4, 4), &value).unwrap();
+    assert_eq!(written, bytes);
+}
+
+#[test]
+fn struct_is_read_to_its_end() {
+    let bytes = bytes();
+    let e = from_reader_with_codecs::<_, Value>(&bytes[..], &codecs(2, 4)).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::SizeMismatch { expected: 4, actual: 2 }), "{}", e);
+    assert_eq!(e.path(), "dna");
+    let e = from_reader_with_codecs::<_, Save>(&bytes[..], &codecs(2, 4)).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::SizeMismatch { expected: 4, actual: 2 }), "{}", e);
+}
+
+#[test]
+fn elements_are_read_to_their_end() {
+    // each element only reads half of itself, which only shows after all of them
+    let bytes = bytes();
+    let e = from_reader_with_codecs::<_, Value>(&bytes[..], &codecs(4, 2)).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::SizeMismatch { expected: 8, actual: 4 }), "{}", e);
+    assert_eq!(e.path(), "genes");
+    let e = from_reader_with_codecs::<_, Save>(&bytes[..], &codecs(4, 2)).unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::SizeMismatch { expected: 8, actual: 4 }), "{}", e);
+}
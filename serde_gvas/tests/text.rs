@@ -0,0 +1,155 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{FText, FormatArgumentValue, Guid, NumberFormattingOptions, Property, TextHistory, Value, from_slice, to_vec};
+
+use common::{header, properties, property, round_trip, string};
+
+fn text(history: TextHistory) -> FText {
+    FText { flags: 0, history }
+}
+
+fn invariant(s: &str) -> Box<FText> {
+    Box::new(text(TextHistory::None { culture_invariant_string: Some(s.to_string()) }))
+}
+
+/// One text of each history, using each kind of format argument
+fn histories() -> Vec<FText> {
+    let options = NumberFormattingOptions {
+        always_sign: true,
+        use_grouping: false,
+        rounding_mode: 2,
+        minimum_integral_digits: 1,
+        maximum_integral_digits: 10,
+        minimum_fractional_digits: 0,
+        maximum_fractional_digits: 3,
+    };
+    vec![
+        text(TextHistory::None { culture_invariant_string: None }),
+        FText { flags: 2, history: TextHistory::None { culture_invariant_string: Some("Hello".to_string()) } },
+        text(TextHistory::Base { namespace: String::new(), key: "Greeting".to_string(), source_string: "Grüße".to_string() }),
+        text(TextHistory::NamedFormat {
+            source_format: invariant("{Name} has {Count}"),
+            arguments: vec![
+                ("Name".to_string(), FormatArgumentValue::Text(invariant("Player"))),
+                ("Count".to_string(), FormatArgumentValue::UInt(u64::MAX)),
+            ],
+        }),
+        text(TextHistory::OrderedFormat {
+            source_format: invariant("{0} {1}"),
+            arguments: vec![FormatArgumentValue::Float(0.5), FormatArgumentValue::Double(-1.25)],
+        }),
+        text(TextHistory::ArgumentFormat {
+            source_format: invariant("{Gender} {Count} {Ratio}"),
+            arguments: vec![
+                ("Gender".to_string(), FormatArgumentValue::Gender(1)),
+                ("Count".to_string(), FormatArgumentValue::Int(-2)),
+                ("Ratio".to_string(), FormatArgumentValue::Float(0.75)),
+            ],
+        }),
+        text(TextHistory::AsNumber { source_value: FormatArgumentValue::Int(-3), format_options: Some(options.clone()), target_culture: "en".to_string() }),
+        text(TextHistory::AsPercent { source_value: FormatArgumentValue::Float(0.25), format_options: None, target_culture: String::new() }),
+        text(TextHistory::AsCurrency {
+            currency_code: "EUR".to_string(),
+            source_value: FormatArgumentValue::Double(9.99),
+            format_options: Some(options),
+            target_culture: "de".to_string(),
+        }),
+        text(TextHistory::AsDate { source_date_time: 636_560_601_022_500_000, date_style: 1, time_zone: "UTC".to_string(), target_culture: String::new() }),
+        text(TextHistory::AsTime { source_date_time: 0, time_style: 2, time_zone: String::new(), target_culture: "fr".to_string() }),
+        text(TextHistory::AsDateTime { source_date_time: -1, date_style: 3, time_style: 4, time_zone: "UTC".to_string(), target_culture: "ja".to_string() }),
+        text(TextHistory::Transform { source_text: invariant("Shout"), transform_type: 1 }),
+        text(TextHistory::StringTableEntry { table_id: "/Game/Strings".to_string(), key: "Title".to_string() }),
+        text(TextHistory::TextGenerator { generator_type: "None".to_string(), contents: Vec::new() }),
+        text(TextHistory::TextGenerator { generator_type: "Counter".to_string(), contents: vec![1, 2, 3] }),
+    ]
+}
+
+fn save(texts: Vec<FText>) -> Value {
+    let properties = texts.into_iter().enumerate().map(|(i, text)| Property::new(format!("Text{}", i), Value::Text(text))).collect();
+    Value::Struct { type_name: String::new(), guid: Guid::default(), properties }
+}
+
+#[test]
+fn wire_format() {
+    let texts = vec![
+        FText { flags: 2, history: TextHistory::None { culture_invariant_string: Some("Hi".to_string()) } },
+        text(TextHistory::Base { namespace: String::new(), key: "K".to_string(), source_string: "Src".to_string() }),
+        text(TextHistory::OrderedFormat { source_format: invariant("{0}"), arguments: vec![FormatArgumentValue::Int(7)] }),
+        text(TextHistory::TextGenerator { generator_type: "None".to_string(), contents: Vec::new() }),
+    ];
+    let bytes = to_vec(&header(), &save(texts)).unwrap();
+    // flags, history type, then the fields of the history
+    let none = [&2u32.to_le_bytes()[..], &[0xff], &1u32.to_le_bytes(), &string("Hi")].concat();
+    let base = [&0u32.to_le_bytes()[..], &[0], &string(""), &string("K"), &string("Src")].concat();
+    let ordered = [
+        &0u32.to_le_bytes()[..], &[2],
+        &0u32.to_le_bytes(), &[0xff], &1u32.to_le_bytes(), &string("{0}"),
+        &1u32.to_le_bytes(), &[0], &7i64.to_le_bytes(),
+    ].concat();
+    let generator = [&0u32.to_le_bytes()[..], &[12], &string("None")].concat();
+    let expected = [
+        property("Text0", "TextProperty", &[], &none),
+        property("Text1", "TextProperty", &[], &base),
+        property("Text2", "TextProperty", &[], &ordered),
+        property("Text3", "TextProperty", &[], &generator),
+    ].concat();
+    assert_eq!(properties(&header(), &bytes), &expected[..]);
+}
+
+#[test]
+fn argument_format() {
+    // an FTextHistory_ArgumentDataFormat, whose FFormatArgumentData have a u8 type and int32 integers
+    let body: &[u8] = b"\x00\x00\x00\x00\x03\
+        \x00\x00\x00\x00\xff\x01\x00\x00\x00\x11\x00\x00\x00{Count} {Gender}\x00\
+        \x02\x00\x00\x00\
+        \x06\x00\x00\x00Count\x00\x00\x2a\x00\x00\x00\
+        \x07\x00\x00\x00Gender\x00\x05\x01";
+    let text = text(TextHistory::ArgumentFormat {
+        source_format: invariant("{Count} {Gender}"),
+        arguments: vec![
+            ("Count".to_string(), FormatArgumentValue::Int(42)),
+            ("Gender".to_string(), FormatArgumentValue::Gender(1)),
+        ],
+    });
+    let bytes = to_vec(&header(), &save(vec![text.clone()])).unwrap();
+    assert_eq!(properties(&header(), &bytes), &property("Text0", "TextProperty", &[], body)[..]);
+    assert_eq!(round_trip(&bytes)["Text0"], Value::Text(text));
+}
+
+#[test]
+fn argument_format_out_of_range() {
+    for argument in &[FormatArgumentValue::Int(1 << 32), FormatArgumentValue::UInt(1), FormatArgumentValue::Double(1.0)] {
+        let text = text(TextHistory::ArgumentFormat { source_format: invariant("{A}"), arguments: vec![("A".to_string(), argument.clone())] });
+        assert!(to_vec(&header(), &save(vec![text])).is_err());
+    }
+}
+
+#[test]
+fn all_histories() {
+    let texts = histories();
+    let bytes = to_vec(&header(), &save(texts.clone())).unwrap();
+    let value = round_trip(&bytes);
+    for (i, text) in texts.into_iter().enumerate() {
+        assert_eq!(value[&format!("Text{}", i)[..]], Value::Text(text));
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    title: FText,
+    lines: Vec<FText>,
+}
+
+#[test]
+fn typed_texts() {
+    let mut lines = histories();
+    let title = lines.remove(2);
+    let value = Save { title, lines };
+    let bytes = to_vec(&header(), &value).unwrap();
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), value);
+    round_trip(&bytes);
+}
@@ -0,0 +1,70 @@
+extern crate serde_gvas;
+#[macro_use]
+extern crate serde_derive;
+
+mod common;
+
+use serde_gvas::{Delegate, FText, FieldPath, Guid, MulticastDelegate, Property, SoftObjectPath, TextHistory, Value,
+    from_slice, from_value, to_vec};
+
+use common::{header, round_trip};
+
+/// Fields of the types which are converted from and to their properties as a whole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Save {
+    text: FText,
+    texts: Vec<FText>,
+    soft_object: SoftObjectPath,
+    delegate: Delegate,
+    delegates: MulticastDelegate,
+    field_path: FieldPath,
+}
+
+fn save() -> Save {
+    let formatted = FText {
+        flags: 1,
+        history: TextHistory::Transform {
+            source_text: Box::new(FText { flags: 0, history: TextHistory::None { culture_invariant_string: None } }),
+            transform_type: 0,
+        },
+    };
+    let delegate = Delegate { object: "/Game/Map.Map:PersistentLevel.Actor_1".to_string(), function_name: "OnHit".to_string() };
+    Save {
+        text: formatted.clone(),
+        texts: vec![formatted, FText { flags: 0, history: TextHistory::StringTableEntry { table_id: "T".to_string(), key: "K".to_string() } }],
+        soft_object: SoftObjectPath { asset_path_name: "/Game/Item.Item".to_string(), sub_path_string: String::new() },
+        delegate: delegate.clone(),
+        delegates: MulticastDelegate(vec![delegate]),
+        field_path: FieldPath { path: vec!["Value".to_string(), "Outer".to_string()], owner: "/Script/Game.Item".to_string() },
+    }
+}
+
+#[test]
+fn typed_round_trip() {
+    let bytes = to_vec(&header(), &save()).unwrap();
+    assert_eq!(from_slice::<Save>(&bytes).unwrap(), save());
+    round_trip(&bytes);
+}
+
+#[test]
+fn from_value_matches_from_slice() {
+    let bytes = to_vec(&header(), &save()).unwrap();
+    let value = round_trip(&bytes);
+    assert_eq!(value["text"], Value::Text(save().text));
+    assert_eq!(from_value::<Save>(value).unwrap(), save());
+}
+
+#[test]
+fn value_round_trip() {
+    let properties = vec![
+        Property::new("text", Value::Text(save().text)),
+        Property::new("soft_class", Value::SoftClass(save().soft_object)),
+        Property::new("delegates", Value::MulticastSparseDelegate(save().delegates)),
+    ];
+    let value = Value::Struct { type_name: String::new(), guid: Guid::default(), properties: properties.clone() };
+    let bytes = to_vec(&header(), &value).unwrap();
+    match round_trip(&bytes) {
+        Value::Struct { properties: parsed, .. } => assert_eq!(parsed, properties),
+        value => panic!("{:?}", value),
+    }
+}
@@ -4,12 +4,13 @@ extern crate serde_gvas;
 #[macro_use]
 extern crate serde_derive;
 
-use serde::{Deserialize, de::value::MapAccessDeserializer};
-use std::io::Read;
-use std::fs::{self, File, FileType};
+use serde_gvas::GvasHeader;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::PathBuf;
 
 #[derive(Deserialize, Debug)]
+#[allow(non_snake_case, dead_code)]
 struct Foo {
     Kills: i32,
     Deaths: i32,
@@ -26,24 +27,22 @@ fn main() {
         if !sav.file_type().unwrap().is_file() {
             continue;
         }
-        let extension = sav.path().extension();
+        let extension = sav.path().extension().map(|ext| ext.to_owned());
         if extension.is_none() || extension.unwrap() != "sav" {
             continue;
         }
 
-        let file = File::open(sav.path());
-        let mut head = [0u8; 47];
-        file.read_exact(&mut head).unwrap();
-        if head[0..4] != b"GVAS" || head[26..31] != b"++RedHarvest+Staging" {
-            continue;
+        let mut file = BufReader::new(File::open(sav.path()).unwrap());
+        match GvasHeader::read(&mut file) {
+            Ok(ref header) if header.engine_version.branch == "++RedHarvest+Staging" => (),
+            _ => continue,
         }
-        let (mut de, _) = serde_gvas::Deserializer::new(file).unwrap();
-        let mut de = MapAccessDeserializer::new(serde_gvas::MapDeserializer::new(&mut de));
-        let foo = Foo::deserialize(de);
-        println!("{:?}", foo);
+        let save = serde_gvas::from_path::<_, Foo>(sav.path());
+        println!("{:?}", save);
     }
 }
 
+#[allow(unused_variables)]
 fn get_path(game: &'static str) -> PathBuf {
     let mut path = std::env::home_dir().unwrap();
      if cfg!(windows) {
@@ -54,4 +53,4 @@ fn get_path(game: &'static str) -> PathBuf {
     path.push("Victory"); path.push("Saved");
     path.push("SaveGames");
     path
-}
\ No newline at end of file
+}